@@ -23,13 +23,13 @@ pub enum CustomErrors {
     #[msg("Provided Accounts should be a multiple of 2")]
     InvalidRemainingAccountsPassed,
 
-    #[msg("Provided minutes in the time should be 00 or 30")]
+    #[msg("Provided time should be the start of a slot")]
     InvalidTime,
 
     #[msg("the iso time string is invalid")]
     InvalidTimeString,
 
-    #[msg("Provided time shouldnt be more than 3 month in future")]
+    #[msg("Provided time is further in the future than the booking window allows")]
     TimeToFarInFuture,
 
     #[msg("this token mint is not supoorted")]
@@ -43,4 +43,157 @@ pub enum CustomErrors {
 
     #[msg("Invalid Received Creator")]
     InvalidReceivedCreator,
+
+    #[msg("Rental has already started")]
+    RentalAlreadyStarted,
+
+    #[msg("Cancellation schedule is invalid")]
+    InvalidCancellationSchedule,
+
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+
+    #[msg("A booking must cover 1 to 255 slots and at most a day")]
+    InvalidSlotCount,
+
+    #[msg("One of the requested slots is already booked")]
+    SlotAlreadyBooked,
+
+    #[msg("Provided day bookings account doesn't match the rental")]
+    InvalidDayBookings,
+
+    #[msg("Rental continues into the next day, its day bookings account is required")]
+    MissingNextDayBookings,
+
+    #[msg("Price must be greater than zero")]
+    ZeroPrice,
+
+    #[msg("Pricing multipliers must be greater than zero")]
+    InvalidPricingMultiplier,
+
+    #[msg("Config account is already in the current layout")]
+    ConfigAlreadyMigrated,
+
+    #[msg("Basis points value can't exceed 10000")]
+    BasisPointsOutOfRange,
+
+    #[msg("Dispute window can't be negative")]
+    InvalidDisputeWindow,
+
+    #[msg("Disputes can only be opened from the rental start until the dispute window ends")]
+    DisputeWindowClosed,
+
+    #[msg("Rental is already disputed")]
+    RentalDisputed,
+
+    #[msg("Rental is not disputed")]
+    RentalNotDisputed,
+
+    #[msg("Authority timelock can't be negative")]
+    InvalidAuthorityTimelock,
+
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+
+    #[msg("Authority transfer timelock has not elapsed yet")]
+    AuthorityTimelockActive,
+
+    #[msg("Config is managed by governance, submit a proposal instead")]
+    ConfigUnderGovernance,
+
+    #[msg("Governance needs 1 to 10 distinct admins and a threshold between 1 and their count")]
+    InvalidGovernanceSettings,
+
+    #[msg("Signer is not a governance admin")]
+    NotGovernanceAdmin,
+
+    #[msg("Admin already approved this proposal")]
+    ProposalAlreadyApproved,
+
+    #[msg("Proposal doesn't have enough approvals yet")]
+    ProposalThresholdNotReached,
+
+    #[msg("New rentals are paused")]
+    RentalsPaused,
+
+    #[msg("Settlements are paused")]
+    SettlementsPaused,
+
+    #[msg("Land is suspended")]
+    LandSuspended,
+
+    #[msg("Config account layout version is not supported")]
+    UnsupportedConfigVersion,
+
+    #[msg("Mint account doesn't match the configured mint")]
+    MintMismatch,
+
+    #[msg("Address can't be the default pubkey")]
+    ZeroAddress,
+
+    #[msg("Slot length must divide an hour or be a whole number of hours up to a day")]
+    InvalidSlotLength,
+
+    #[msg("Booking window must be positive and longer than the minimum lead time")]
+    InvalidBookingWindow,
+
+    #[msg("Rental starts sooner than the minimum lead time")]
+    BookingTooSoon,
+
+    #[msg("Rental can't start in the past")]
+    BookingInPast,
+
+    #[msg("Opening hours must fit in 24 bits and blackouts must be at most 16 non-empty periods")]
+    InvalidAvailability,
+
+    #[msg("Land is not available for the requested slots")]
+    LandUnavailable,
+
+    #[msg("Rental is waiting for the land owner approval")]
+    RentalPending,
+
+    #[msg("Rental is not waiting for approval")]
+    RentalNotPending,
+
+    #[msg("Rental request can no longer be approved")]
+    ApprovalDeadlinePassed,
+
+    #[msg("Rental request can still be answered by the land owner")]
+    ApprovalDeadlineNotReached,
+
+    #[msg("Rental has already ended")]
+    RentalEnded,
+
+    #[msg("Rental has not started yet")]
+    RentalNotStarted,
+
+    #[msg("Proposal has expired")]
+    ProposalExpired,
+
+    #[msg("Only the proposer can cancel a proposal before it expires")]
+    ProposalNotExpired,
+
+    #[msg("A mint change must set the base cost in the new mint")]
+    MintChangeWithoutBaseCost,
+
+    #[msg("Base cost is set by the pricing admin unless the mint changes")]
+    BaseCostWithoutMintChange,
+
+    #[msg("Mint can't change while rental escrows are open")]
+    EscrowsStillOpen,
+
+    #[msg("Day bookings account is required while the rental has not ended")]
+    MissingDayBookings,
+
+    #[msg("Bookings of a day can only be closed once the following day has ended")]
+    DayBookingsInUse,
+
+    #[msg("A legacy escrow is passed more than once")]
+    DuplicateLegacyEscrow,
+
+    #[msg("Rental cost is above the maximum accepted by the renter")]
+    CostAboveMaximum,
+
+    #[msg("Authority can't change once governance is set up")]
+    AuthorityUnderGovernance,
 }
@@ -0,0 +1,260 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Blackout, Data};
+
+#[event]
+pub struct ConfigInitialized {
+    pub centralized_account: Pubkey,
+    pub fee_account: Pubkey,
+    pub mint_address: Pubkey,
+    pub base_cost: u64,
+    pub admin_quota_bps: u16,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub old: Data,
+    pub new: Data,
+}
+
+#[event]
+pub struct ConfigMigrated {
+    pub centralized_account: Pubkey,
+    /// Legacy escrows still open, counted in `Data::open_escrows`
+    pub open_escrows: u64,
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub current_authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub eta: i64,
+}
+
+#[event]
+pub struct AuthorityProposalCancelled {
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct GovernanceInitialized {
+    pub admins: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct ConfigProposed {
+    pub proposal: Pubkey,
+    pub id: u64,
+    pub proposer: Pubkey,
+}
+
+#[event]
+pub struct ConfigProposalApproved {
+    pub proposal: Pubkey,
+    pub admin: Pubkey,
+    pub approvals: u8,
+}
+
+#[event]
+pub struct ConfigProposalCancelled {
+    pub proposal: Pubkey,
+    pub id: u64,
+    pub expired: bool,
+}
+
+#[event]
+pub struct GovernanceUpdated {
+    pub admins: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct PauseUpdated {
+    pub rentals_paused: bool,
+    pub settlements_paused: bool,
+}
+
+#[event]
+pub struct LandStatusUpdated {
+    pub land_asset_id: Pubkey,
+    pub suspended: bool,
+}
+
+#[event]
+pub struct RentalMinted {
+    pub land_asset_id: Pubkey,
+    pub rental_asset_id: Pubkey,
+    pub rent_escrow: Pubkey,
+    pub renter: Pubkey,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub expected_cost: u64,
+    pub fee_quota: u64,
+}
+
+/// Emitted once per escrow, whether settled alone or in a batch
+#[event]
+pub struct RentalSettled {
+    pub land_asset_id: Pubkey,
+    pub rent_escrow: Pubkey,
+    pub payment_receiver: Pubkey,
+    pub expected_cost: u64,
+    pub fee_quota: u64,
+}
+
+/// Part of the fees of `escrow_count` settled escrows paid to the keeper
+#[event]
+pub struct KeeperRewarded {
+    pub keeper: Pubkey,
+    pub reward: u64,
+    pub escrow_count: u32,
+}
+
+#[event]
+pub struct RentalRequested {
+    pub land_asset_id: Pubkey,
+    pub rent_escrow: Pubkey,
+    pub renter: Pubkey,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub expected_cost: u64,
+    pub approval_deadline: i64,
+}
+
+#[event]
+pub struct RentalApproved {
+    pub land_asset_id: Pubkey,
+    pub rental_asset_id: Pubkey,
+    pub rent_escrow: Pubkey,
+    pub renter: Pubkey,
+}
+
+/// Emitted when a rental request is rejected by the land owner, or left unanswered
+#[event]
+pub struct RentalRejected {
+    pub land_asset_id: Pubkey,
+    pub rent_escrow: Pubkey,
+    pub renter: Pubkey,
+    pub refund: u64,
+    pub expired: bool,
+}
+
+#[event]
+pub struct LandBookingModeSet {
+    pub land_asset_id: Pubkey,
+    pub owner: Pubkey,
+    pub approval_required: bool,
+}
+
+#[event]
+pub struct RentalExtended {
+    pub land_asset_id: Pubkey,
+    pub rental_asset_id: Pubkey,
+    pub rent_escrow: Pubkey,
+    pub renter: Pubkey,
+    pub end_time: i64,
+    pub additional_cost: u64,
+    pub additional_fee: u64,
+}
+
+/// `paid` covers the `used_slots` slots that started, `fee` included
+#[event]
+pub struct RentalEndedEarly {
+    pub land_asset_id: Pubkey,
+    pub rental_asset_id: Pubkey,
+    pub rent_escrow: Pubkey,
+    pub renter: Pubkey,
+    pub payment_receiver: Pubkey,
+    pub used_slots: u32,
+    pub paid: u64,
+    pub fee: u64,
+    pub refund: u64,
+}
+
+/// `royalty` is the part of `price` paid to the land payment receiver
+#[event]
+pub struct RentalTransferred {
+    pub land_asset_id: Pubkey,
+    pub rental_asset_id: Pubkey,
+    pub rent_escrow: Pubkey,
+    pub previous_renter: Pubkey,
+    pub renter: Pubkey,
+    pub price: u64,
+    pub royalty: u64,
+}
+
+#[event]
+pub struct RentalCancelled {
+    pub land_asset_id: Pubkey,
+    pub rental_asset_id: Pubkey,
+    pub rent_escrow: Pubkey,
+    pub renter: Pubkey,
+    pub payment_receiver: Pubkey,
+    pub refund: u64,
+    /// Part of the rental cost kept, `fee` of it going to the fee account
+    pub retained: u64,
+    pub fee: u64,
+}
+
+#[event]
+pub struct RentalVoided {
+    pub land_asset_id: Pubkey,
+    pub rental_asset_id: Pubkey,
+    pub rent_escrow: Pubkey,
+    pub renter: Pubkey,
+    pub refund: u64,
+    pub reason: u8,
+}
+
+#[event]
+pub struct DisputeOpened {
+    pub land_asset_id: Pubkey,
+    pub rent_escrow: Pubkey,
+    pub renter: Pubkey,
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub land_asset_id: Pubkey,
+    pub rent_escrow: Pubkey,
+    pub arbiter: Pubkey,
+    pub refund: u64,
+    pub fee: u64,
+    pub owner_payment: u64,
+}
+
+#[event]
+pub struct LandPricingSet {
+    pub land_asset_id: Pubkey,
+    pub owner: Pubkey,
+    pub price: u64,
+}
+
+#[event]
+pub struct LandAvailabilitySet {
+    pub land_asset_id: Pubkey,
+    pub owner: Pubkey,
+    pub weekly_hours: [u32; 7],
+    pub blackouts: Vec<Blackout>,
+}
+
+#[event]
+pub struct DayBookingsClosed {
+    pub land_asset_id: Pubkey,
+    pub day: i64,
+}
+
+#[event]
+pub struct LegacyBookingBackfilled {
+    pub land_asset_id: Pubkey,
+    pub rent_escrow: Pubkey,
+    pub start_time: i64,
+    pub end_time: i64,
+}
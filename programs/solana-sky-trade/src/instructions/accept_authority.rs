@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::*, events::*, state::*};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptAuthorityPayload<'info> {
+    #[account(
+        mut,
+        seeds = [b"central_authority"],
+        bump
+        )]
+    pub central_authority: Box<Account<'info, Data>>,
+
+    pub pending_authority: Signer<'info>,
+}
+
+/// Second step of replacing the `centralized_account`, signed by the proposed key once the
+/// timelock has elapsed. A proposal left pending when governance was set up can't be accepted.
+pub fn handle_accept_authority(ctx: Context<AcceptAuthorityPayload>) -> Result<()> {
+    let data = &mut ctx.accounts.central_authority;
+
+    if data.config_governed {
+        return err!(CustomErrors::AuthorityUnderGovernance);
+    }
+
+    if data.pending_authority == Pubkey::default() {
+        return err!(CustomErrors::NoPendingAuthority);
+    }
+    require_keys_eq!(
+        data.pending_authority,
+        ctx.accounts.pending_authority.key(),
+        CustomErrors::InvalidAuthority
+    );
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    if current_timestamp < data.pending_authority_eta {
+        return err!(CustomErrors::AuthorityTimelockActive);
+    }
+
+    let old_authority = data.centralized_account;
+    data.centralized_account = data.pending_authority;
+    data.pending_authority = Pubkey::default();
+    data.pending_authority_eta = 0;
+
+    emit_cpi!(AuthorityTransferred {
+        old_authority,
+        new_authority: data.centralized_account,
+    });
+
+    Ok(())
+}
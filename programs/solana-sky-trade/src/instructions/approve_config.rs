@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::*, events::*, state::*};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ApproveConfigPayload<'info> {
+    #[account(
+        seeds = [b"governance"],
+        bump = governance.bump
+    )]
+    pub governance: Box<Account<'info, Governance>>,
+
+    #[account(
+        mut,
+        seeds = [b"config_proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Box<Account<'info, ConfigProposal>>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handle_approve_config(ctx: Context<ApproveConfigPayload>) -> Result<()> {
+    let admin = ctx.accounts.admin.key();
+    ctx.accounts.governance.check_admin(admin)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    if proposal.is_expired(Clock::get()?.unix_timestamp) {
+        return err!(CustomErrors::ProposalExpired);
+    }
+
+    // Approvals of removed admins no longer count, nor take room
+    let governance = &ctx.accounts.governance;
+    proposal
+        .approvals
+        .retain(|approval| governance.admins.contains(approval));
+    proposal.approve(admin)?;
+
+    emit_cpi!(ConfigProposalApproved {
+        proposal: proposal.key(),
+        admin,
+        approvals: proposal.approvals.len() as u8,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+use mpl_bubblegum::{
+    accounts::TreeConfig, instructions::MintToCollectionV1CpiBuilder, types::MetadataArgs,
+    utils::get_asset_id,
+};
+
+use crate::{errors::*, events::*, state::*, Metadata};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ApproveRentalAccounts<'info> {
+    #[account(
+        seeds = [b"central_authority"],
+        bump
+        )]
+    pub central_authority: Box<Account<'info, Data>>,
+
+    #[account(mut)]
+    pub minting_signer: Signer<'info>,
+
+    pub land_owner: Signer<'info>,
+
+    /// CHECK: checked at ix
+    pub land_delegate: UncheckedAccount<'info>,
+
+    /// CHECK: This account is checked in the instruction
+    pub land_merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: checked against the escrow
+    pub renter: UncheckedAccount<'info>,
+
+    #[account(mut, has_one = renter)]
+    pub rent_escrow: Box<Account<'info, RentalEscrow>>,
+
+    /// CHECK: checked by bubblegum
+    #[account(mut)]
+    pub rental_merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: checked by bubblegum
+    #[account(mut)]
+    pub tree_config: UncheckedAccount<'info>,
+
+    /// CHECK: checked by bubblegum
+    pub collection_mint: UncheckedAccount<'info>,
+
+    /// CHECK: checked by bubblegum
+    #[account(mut)]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: checked by bubblegum
+    pub collection_edition: UncheckedAccount<'info>,
+
+    /// CHECK: used to sign creation
+    pub bubblegum_signer: UncheckedAccount<'info>,
+
+    pub bubblegum_program: Program<'info, MplBubblegumProgramAccount>,
+    pub log_wrapper: Program<'info, NoopProgramAccount>,
+    pub compression_program: Program<'info, SplAccountCompressionProgramAccount>,
+    pub system_program: Program<'info, System>,
+    pub token_metadata_program: Program<'info, Metadata>,
+}
+
+/// Accepts a rental request and mints its rental cNFT to the renter. Only the current owner of
+/// the land cNFT can approve, before the request deadline.
+pub fn handle_approve_rental<'info>(
+    ctx: Context<'_, '_, '_, 'info, ApproveRentalAccounts<'info>>,
+    mint_metadata_args: Vec<u8>,
+    land_asset_id_leaf_data: LeafData,
+) -> Result<()> {
+    let central_authority = &ctx.accounts.central_authority;
+    central_authority.check_minting_signer(ctx.accounts.minting_signer.key())?;
+
+    let escrow = &ctx.accounts.rent_escrow;
+    if !escrow.is_pending() {
+        return err!(CustomErrors::RentalNotPending);
+    }
+    if Clock::get()?.unix_timestamp >= escrow.approval_deadline {
+        return err!(CustomErrors::ApprovalDeadlinePassed);
+    }
+
+    let asset_id = get_asset_id(
+        &ctx.accounts.land_merkle_tree.key(),
+        land_asset_id_leaf_data.nonce,
+    );
+    require_keys_eq!(escrow.land_asset_id, asset_id);
+
+    central_authority.check_received_creator_hash(&land_asset_id_leaf_data.creator_hash)?;
+    let proof: Vec<_> = ctx
+        .remaining_accounts
+        .iter()
+        .map(|account| (account, false, false))
+        .collect();
+    land_asset_id_leaf_data.verify_leaf(
+        &ctx.accounts.compression_program.to_account_info(),
+        &ctx.accounts.land_merkle_tree.to_account_info(),
+        escrow.land_asset_id,
+        ctx.accounts.land_owner.key(),
+        ctx.accounts.land_delegate.key(),
+        &proof,
+    )?;
+
+    let mint_metadata = MetadataArgs::try_from_slice(mint_metadata_args.as_slice())?;
+
+    // The rental cNFT takes the next nonce of the rental tree
+    let tree_config = TreeConfig::try_from(&ctx.accounts.tree_config.to_account_info())?;
+    let rental_asset_id = get_asset_id(
+        &ctx.accounts.rental_merkle_tree.key(),
+        tree_config.num_minted,
+    );
+
+    MintToCollectionV1CpiBuilder::new(&ctx.accounts.bubblegum_program.to_account_info())
+        .tree_config(&ctx.accounts.tree_config.to_account_info())
+        .leaf_owner(&ctx.accounts.renter.to_account_info())
+        .leaf_delegate(&ctx.accounts.minting_signer.to_account_info())
+        .merkle_tree(&ctx.accounts.rental_merkle_tree.to_account_info())
+        .payer(&ctx.accounts.minting_signer.to_account_info())
+        .tree_creator_or_delegate(&ctx.accounts.minting_signer.to_account_info())
+        .collection_authority(&ctx.accounts.minting_signer.to_account_info())
+        .collection_mint(&ctx.accounts.collection_mint.to_account_info())
+        .collection_metadata(&ctx.accounts.collection_metadata.to_account_info())
+        .collection_edition(&ctx.accounts.collection_edition.to_account_info())
+        .log_wrapper(&ctx.accounts.log_wrapper.to_account_info())
+        .compression_program(&ctx.accounts.compression_program.to_account_info())
+        .system_program(&ctx.accounts.system_program.to_account_info())
+        .bubblegum_signer(&ctx.accounts.bubblegum_signer.to_account_info())
+        .token_metadata_program(&ctx.accounts.token_metadata_program.to_account_info())
+        .metadata(mint_metadata)
+        .invoke()?;
+
+    let escrow = &mut ctx.accounts.rent_escrow;
+    escrow.rental_asset_id = rental_asset_id;
+    escrow.rental_merkle_tree = ctx.accounts.rental_merkle_tree.key();
+    escrow.approval_deadline = 0;
+
+    emit_cpi!(RentalApproved {
+        land_asset_id: escrow.land_asset_id,
+        rental_asset_id,
+        rent_escrow: escrow.key(),
+        renter: escrow.renter,
+    });
+
+    Ok(())
+}
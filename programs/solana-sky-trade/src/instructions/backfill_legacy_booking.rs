@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use chrono::DateTime;
+
+use crate::{errors::*, events::*, state::*};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(start_time: i64)]
+pub struct BackfillLegacyBookingPayload<'info> {
+    #[account(
+        seeds = [b"central_authority"],
+        bump
+        )]
+    pub central_authority: Box<Account<'info, Data>>,
+
+    pub rent_escrow: Box<Account<'info, RentEscrow>>,
+
+    #[account(
+        init_if_needed,
+        payer = minting_signer,
+        space = DayBookings::MAX_SIZE,
+        seeds = [
+            b"bookings",
+            rent_escrow.land_asset_id.as_ref(),
+            &DayBookings::seed_day(start_time, 0),
+        ],
+        bump
+    )]
+    pub day_bookings: Box<Account<'info, DayBookings>>,
+
+    /// Only required when the rental continues past midnight
+    #[account(
+        init_if_needed,
+        payer = minting_signer,
+        space = DayBookings::MAX_SIZE,
+        seeds = [
+            b"bookings",
+            rent_escrow.land_asset_id.as_ref(),
+            &DayBookings::seed_day(start_time, 1),
+        ],
+        bump
+    )]
+    pub next_day_bookings: Option<Box<Account<'info, DayBookings>>>,
+
+    /// Pays the rent of new bookings accounts, which `close_day_bookings` returns to it
+    #[account(mut)]
+    pub minting_signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Records the slots of a legacy `RentEscrow` in the `DayBookings` of its land, so they can't be
+/// booked again. The minting signer backfills escrows that have not ended, paying the rent of new
+/// bookings accounts like it does for new rentals.
+///
+/// `start_time` is the `creation_time` of the escrow as a unix timestamp, used to derive the
+/// bookings accounts. Overlapping legacy escrows can't both be backfilled.
+pub fn handle_backfill_legacy_booking(
+    ctx: Context<BackfillLegacyBookingPayload>,
+    start_time: i64,
+) -> Result<()> {
+    ctx.accounts
+        .central_authority
+        .check_minting_signer(ctx.accounts.minting_signer.key())?;
+
+    let escrow = &ctx.accounts.rent_escrow;
+    let parse_time = |time: &str| {
+        DateTime::parse_from_rfc3339(time)
+            .map(|time| time.timestamp())
+            .map_err(|_| error!(CustomErrors::InvalidTimeString))
+    };
+
+    require_eq!(
+        parse_time(&escrow.creation_time)?,
+        start_time,
+        CustomErrors::InvalidTime
+    );
+    let end_time = parse_time(&escrow.end_time)?;
+    if Clock::get()?.unix_timestamp >= end_time {
+        return err!(CustomErrors::RentalEnded);
+    }
+
+    let land_asset_id = escrow.land_asset_id;
+    book_slots(
+        &mut ctx.accounts.day_bookings,
+        ctx.accounts
+            .next_day_bookings
+            .as_deref_mut()
+            .map(|bookings| &mut **bookings),
+        land_asset_id,
+        start_time,
+        end_time,
+    )?;
+
+    emit_cpi!(LegacyBookingBackfilled {
+        land_asset_id,
+        rent_escrow: ctx.accounts.rent_escrow.key(),
+        start_time,
+        end_time,
+    });
+
+    Ok(())
+}
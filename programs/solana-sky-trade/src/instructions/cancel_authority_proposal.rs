@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::*, events::*, state::*};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelAuthorityProposalPayload<'info> {
+    #[account(
+        mut,
+        seeds = [b"central_authority"],
+        bump
+        )]
+    pub central_authority: Box<Account<'info, Data>>,
+
+    pub centralized_account: Signer<'info>,
+}
+
+/// Drops the pending authority transfer, if any.
+pub fn handle_cancel_authority_proposal(
+    ctx: Context<CancelAuthorityProposalPayload>,
+) -> Result<()> {
+    let data = &mut ctx.accounts.central_authority;
+    data.check_authority(ctx.accounts.centralized_account.key())?;
+
+    if data.pending_authority == Pubkey::default() {
+        return err!(CustomErrors::NoPendingAuthority);
+    }
+
+    let pending_authority = data.pending_authority;
+    data.pending_authority = Pubkey::default();
+    data.pending_authority_eta = 0;
+
+    emit_cpi!(AuthorityProposalCancelled { pending_authority });
+
+    Ok(())
+}
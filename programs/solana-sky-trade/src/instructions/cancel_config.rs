@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::*, events::*, state::*};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelConfigPayload<'info> {
+    #[account(
+        mut,
+        has_one = proposer,
+        close = proposer,
+        seeds = [b"config_proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Box<Account<'info, ConfigProposal>>,
+
+    /// CHECK: receives the proposal rent, checked against the proposal
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+
+    pub closer: Signer<'info>,
+}
+
+/// Drops a proposal. The proposer can cancel it at any time, anyone else only once it expired.
+pub fn handle_cancel_config(ctx: Context<CancelConfigPayload>) -> Result<()> {
+    let proposal = &ctx.accounts.proposal;
+    let expired = proposal.is_expired(Clock::get()?.unix_timestamp);
+    if !expired && ctx.accounts.closer.key() != proposal.proposer {
+        return err!(CustomErrors::ProposalNotExpired);
+    }
+
+    emit_cpi!(ConfigProposalCancelled {
+        proposal: proposal.key(),
+        id: proposal.id,
+        expired,
+    });
+
+    Ok(())
+}
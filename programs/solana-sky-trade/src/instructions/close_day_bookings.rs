@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::*, events::*, state::*};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseDayBookingsPayload<'info> {
+    #[account(
+        seeds = [b"central_authority"],
+        bump
+        )]
+    pub central_authority: Box<Account<'info, Data>>,
+
+    #[account(
+        mut,
+        close = minting_signer,
+        seeds = [
+            b"bookings",
+            day_bookings.land_asset_id.as_ref(),
+            &day_bookings.day.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub day_bookings: Box<Account<'info, DayBookings>>,
+
+    /// CHECK: paid the rent of the bookings, checked at ix
+    #[account(mut)]
+    pub minting_signer: UncheckedAccount<'info>,
+}
+
+/// Closes the bookings of a past day, returning their rent to the minting signer. Anyone can
+/// close them.
+///
+/// A rental spans at most a full day, so once the day after has ended too, no rental booked on
+/// the day can still be cancelled, ended early or extended.
+pub fn handle_close_day_bookings(ctx: Context<CloseDayBookingsPayload>) -> Result<()> {
+    ctx.accounts
+        .central_authority
+        .check_minting_signer(ctx.accounts.minting_signer.key())?;
+
+    let day_bookings = &ctx.accounts.day_bookings;
+    if Clock::get()?.unix_timestamp < (day_bookings.day + 2) * SECONDS_PER_DAY {
+        return err!(CustomErrors::DayBookingsInUse);
+    }
+
+    emit_cpi!(DayBookingsClosed {
+        land_asset_id: day_bookings.land_asset_id,
+        day: day_bookings.day,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,331 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, transfer, CloseAccount, Mint, Token, TokenAccount, Transfer},
+};
+use mpl_bubblegum::{instructions::BurnCpiBuilder, utils::get_asset_id};
+
+use crate::{errors::*, events::*, state::*};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct EndRentalEarlyAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"central_authority"],
+        bump
+        )]
+    pub central_authority: Box<Account<'info, Data>>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub renter: Signer<'info>,
+
+    #[account(mut,
+        associated_token::mint = mint,
+        associated_token::authority = renter
+    )]
+    pub renter_ata: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: checked at ix
+    #[account(mut)]
+    pub fee_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = fee_account,
+    )]
+    pub fee_account_ata: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: checked at ix
+    pub land_owner: UncheckedAccount<'info>,
+
+    /// CHECK: checked at ix
+    pub land_delegate: UncheckedAccount<'info>,
+
+    /// CHECK: checked at ix
+    pub payment_receiver: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = payment_receiver,
+    )]
+    pub payment_receiver_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut,
+        has_one = renter,
+        has_one = renter_ata,
+        has_one = rental_merkle_tree,
+        close = fee_account,
+    )]
+    pub rent_escrow: Box<Account<'info, RentalEscrow>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = rent_escrow,
+    )]
+    pub rent_escrow_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"bookings",
+            rent_escrow.land_asset_id.as_ref(),
+            &DayBookings::seed_day(rent_escrow.start_time, 0),
+        ],
+        bump
+    )]
+    pub day_bookings: Box<Account<'info, DayBookings>>,
+
+    /// Only required when the rental continues past midnight
+    #[account(
+        mut,
+        seeds = [
+            b"bookings",
+            rent_escrow.land_asset_id.as_ref(),
+            &DayBookings::seed_day(rent_escrow.start_time, 1),
+        ],
+        bump
+    )]
+    pub next_day_bookings: Option<Box<Account<'info, DayBookings>>>,
+
+    /// CHECK: may not be initialized, the land is active unless suspended by an admin
+    #[account(
+        seeds = [b"land_status", rent_escrow.land_asset_id.as_ref()],
+        bump
+    )]
+    pub land_status: UncheckedAccount<'info>,
+
+    /// CHECK: This account is checked in the instruction
+    pub land_merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: delegate of the rental cNFT, checked by bubblegum
+    pub rental_delegate: UncheckedAccount<'info>,
+
+    /// CHECK: checked by bubblegum
+    #[account(mut)]
+    pub rental_merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: checked by bubblegum
+    pub tree_config: UncheckedAccount<'info>,
+
+    pub bubblegum_program: Program<'info, MplBubblegumProgramAccount>,
+    pub log_wrapper: Program<'info, NoopProgramAccount>,
+    pub compression_program: Program<'info, SplAccountCompressionProgramAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> EndRentalEarlyAccounts<'info> {
+    fn transfer_ctx(
+        &self,
+        to: &Account<'info, TokenAccount>,
+    ) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.rent_escrow_ata.to_account_info(),
+                to: to.to_account_info(),
+                authority: self.rent_escrow.to_account_info(),
+            },
+        )
+    }
+
+    fn close_ata_context(&self) -> CpiContext<'_, '_, '_, 'info, CloseAccount<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.rent_escrow_ata.to_account_info(),
+                destination: self.fee_account.to_account_info(),
+                authority: self.rent_escrow.to_account_info(),
+            },
+        )
+    }
+}
+
+/// Ends a running rental before its end time, burning the rental cNFT.
+///
+/// Slots that started, the current one included, are paid out like `transfer_on_expiry` does,
+/// pro rata of the cost and fee locked at booking. The rest of the rental cost is refunded to the
+/// renter and the unused slots are freed.
+///
+/// `remaining_accounts` holds the `land_proof_len` proof nodes of the land leaf, followed by the
+/// proof of the rental leaf.
+pub fn handle_end_rental_early<'info>(
+    ctx: Context<'_, '_, '_, 'info, EndRentalEarlyAccounts<'info>>,
+    land_asset_id_leaf_data: LeafData,
+    rental_leaf_data: LeafData,
+    land_proof_len: u8,
+) -> Result<()> {
+    let central_authority = &ctx.accounts.central_authority;
+    if ctx.accounts.fee_account.key() != central_authority.fee_account {
+        return err!(CustomErrors::InvalidReceiver);
+    }
+
+    if ctx.accounts.mint.key() != central_authority.mint_address {
+        return err!(CustomErrors::InvalidMint);
+    }
+
+    if central_authority.settlements_paused {
+        return err!(CustomErrors::SettlementsPaused);
+    }
+    if LandStatus::is_suspended(&ctx.accounts.land_status)? {
+        return err!(CustomErrors::LandSuspended);
+    }
+
+    let escrow = &ctx.accounts.rent_escrow;
+    if escrow.is_pending() {
+        return err!(CustomErrors::RentalPending);
+    }
+    if escrow.disputed {
+        return err!(CustomErrors::RentalDisputed);
+    }
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    if current_timestamp < escrow.start_time {
+        return err!(CustomErrors::RentalNotStarted);
+    }
+    if current_timestamp >= escrow.end_time {
+        return err!(CustomErrors::RentalEnded);
+    }
+
+    let land_proof_len = land_proof_len as usize;
+    if ctx.remaining_accounts.len() < land_proof_len {
+        return err!(CustomErrors::InvalidRemainingAccountsPassed);
+    }
+    let (land_proof, rental_proof) = ctx.remaining_accounts.split_at(land_proof_len);
+
+    let asset_id = get_asset_id(
+        &ctx.accounts.land_merkle_tree.key(),
+        land_asset_id_leaf_data.nonce,
+    );
+    require_keys_eq!(escrow.land_asset_id, asset_id);
+
+    central_authority.check_received_creator_hash(&land_asset_id_leaf_data.creator_hash)?;
+    let land_proof: Vec<_> = land_proof
+        .iter()
+        .map(|account| (account, false, false))
+        .collect();
+    land_asset_id_leaf_data.verify_leaf(
+        &ctx.accounts.compression_program.to_account_info(),
+        &ctx.accounts.land_merkle_tree.to_account_info(),
+        escrow.land_asset_id,
+        ctx.accounts.land_owner.key(),
+        ctx.accounts.land_delegate.key(),
+        &land_proof,
+    )?;
+
+    central_authority.check_payment_receiver(
+        &ctx.accounts.land_owner,
+        ctx.accounts.payment_receiver.key(),
+    )?;
+
+    let asset_id = get_asset_id(
+        &ctx.accounts.rental_merkle_tree.key(),
+        rental_leaf_data.nonce,
+    );
+    if asset_id != escrow.rental_asset_id {
+        return err!(CustomErrors::InvalidRentalAddressPassed);
+    }
+
+    // The slot in progress counts as used
+    let slot_count = escrow.slot_count();
+    let used_slots =
+        ((current_timestamp - escrow.start_time) / escrow.slot_seconds + 1).min(slot_count);
+    let used_cost = pro_rata_share(escrow.expected_cost, used_slots as u64, slot_count as u64)?;
+    let fee = pro_rata_share(escrow.fee_quota, used_slots as u64, slot_count as u64)?;
+    let final_payment = used_cost - fee;
+    let refund = escrow.expected_cost - used_cost;
+
+    msg!("used slots {}", used_slots);
+    msg!("refund {}", refund);
+
+    // Free the unused slots, which may all fall on the next day. None are left when the rental is
+    // ended during its last slot.
+    let land_asset_id = escrow.land_asset_id;
+    let unused_start = escrow.start_time + used_slots * escrow.slot_seconds;
+    let end_time = escrow.end_time;
+    if unused_start < end_time {
+        if unused_start.div_euclid(SECONDS_PER_DAY) == escrow.start_time.div_euclid(SECONDS_PER_DAY)
+        {
+            release_slots(
+                &mut ctx.accounts.day_bookings,
+                ctx.accounts
+                    .next_day_bookings
+                    .as_deref_mut()
+                    .map(|bookings| &mut **bookings),
+                land_asset_id,
+                unused_start,
+                end_time,
+            )?;
+        } else {
+            let next_day_bookings = ctx
+                .accounts
+                .next_day_bookings
+                .as_deref_mut()
+                .ok_or(CustomErrors::MissingNextDayBookings)?;
+            release_slots(
+                next_day_bookings,
+                None,
+                land_asset_id,
+                unused_start,
+                end_time,
+            )?;
+        }
+    }
+
+    // Bubblegum checks that the renter still owns the rental cNFT
+    let rental_proof: Vec<_> = rental_proof
+        .iter()
+        .map(|account| (account, false, false))
+        .collect();
+    BurnCpiBuilder::new(&ctx.accounts.bubblegum_program.to_account_info())
+        .tree_config(&ctx.accounts.tree_config.to_account_info())
+        .leaf_owner(&ctx.accounts.renter.to_account_info(), true)
+        .leaf_delegate(&ctx.accounts.rental_delegate.to_account_info(), false)
+        .merkle_tree(&ctx.accounts.rental_merkle_tree.to_account_info())
+        .log_wrapper(&ctx.accounts.log_wrapper.to_account_info())
+        .compression_program(&ctx.accounts.compression_program.to_account_info())
+        .system_program(&ctx.accounts.system_program.to_account_info())
+        .root(rental_leaf_data.root)
+        .data_hash(rental_leaf_data.hash)
+        .creator_hash(rental_leaf_data.creator_hash)
+        .nonce(rental_leaf_data.nonce)
+        .index(rental_leaf_data.index)
+        .add_remaining_accounts(&rental_proof)
+        .invoke()?;
+
+    let escrow = &ctx.accounts.rent_escrow;
+    let seeds = escrow.escrow_seeds();
+    for (to, amount) in [
+        (&ctx.accounts.fee_account_ata, fee),
+        (&ctx.accounts.payment_receiver_ata, final_payment),
+        (&ctx.accounts.renter_ata, refund),
+    ] {
+        if amount > 0 {
+            transfer(ctx.accounts.transfer_ctx(to).with_signer(&[&seeds]), amount)?;
+        }
+    }
+
+    token::close_account(ctx.accounts.close_ata_context().with_signer(&[&seeds]))?;
+    ctx.accounts.central_authority.escrow_closed()?;
+
+    emit_cpi!(RentalEndedEarly {
+        land_asset_id,
+        rental_asset_id: escrow.rental_asset_id,
+        rent_escrow: escrow.key(),
+        renter: escrow.renter,
+        payment_receiver: ctx.accounts.payment_receiver.key(),
+        used_slots: used_slots as u32,
+        paid: used_cost,
+        fee,
+        refund,
+    });
+
+    Ok(())
+}
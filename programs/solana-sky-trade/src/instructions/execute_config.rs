@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{errors::*, events::*, state::*};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteConfigPayload<'info> {
+    #[account(
+        mut,
+        seeds = [b"central_authority"],
+        bump
+        )]
+    pub central_authority: Box<Account<'info, Data>>,
+
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance.bump
+    )]
+    pub governance: Box<Account<'info, Governance>>,
+
+    #[account(
+        mut,
+        has_one = proposer,
+        close = proposer,
+        seeds = [b"config_proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Box<Account<'info, ConfigProposal>>,
+
+    /// CHECK: receives the proposal rent, checked against the proposal
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+
+    pub mint_account: Account<'info, Mint>,
+}
+
+/// Applies a proposal that reached the governance threshold before expiring. Anyone can execute
+/// it.
+pub fn handle_execute_config(ctx: Context<ExecuteConfigPayload>) -> Result<()> {
+    if ctx
+        .accounts
+        .proposal
+        .is_expired(Clock::get()?.unix_timestamp)
+    {
+        return err!(CustomErrors::ProposalExpired);
+    }
+
+    let governance = &ctx.accounts.governance;
+    if governance.count_approvals(&ctx.accounts.proposal) < governance.threshold as usize {
+        return err!(CustomErrors::ProposalThresholdNotReached);
+    }
+
+    let old = (**ctx.accounts.central_authority).clone();
+    ctx.accounts
+        .proposal
+        .apply(&mut ctx.accounts.central_authority)?;
+    ctx.accounts
+        .central_authority
+        .check_mint_account(&ctx.accounts.mint_account)?;
+
+    emit_cpi!(ConfigUpdated {
+        old,
+        new: (**ctx.accounts.central_authority).clone(),
+    });
+
+    if let Some(settings) = ctx.accounts.proposal.governance_changes.clone() {
+        let governance = &mut ctx.accounts.governance;
+        governance.admins = settings.admins.clone();
+        governance.threshold = settings.threshold;
+
+        emit_cpi!(GovernanceUpdated {
+            admins: settings.admins,
+            threshold: settings.threshold,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,326 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+use mpl_bubblegum::{
+    instructions::UpdateMetadataCpiBuilder,
+    types::{MetadataArgs, UpdateArgs},
+    utils::get_asset_id,
+};
+
+use crate::{errors::*, events::*, state::*, Metadata};
+
+/// New metadata of the rental cNFT, reflecting the extended end time
+#[derive(Clone, AnchorDeserialize, AnchorSerialize)]
+pub struct RentalMetadataUpdate {
+    pub rental_leaf_data: LeafData,
+    /// Serialized bubblegum `MetadataArgs` the rental cNFT currently holds
+    pub current_metadata_args: Vec<u8>,
+    /// Serialized bubblegum `UpdateArgs` to apply
+    pub update_args: Vec<u8>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExtendRentalAccounts<'info> {
+    #[account(
+        seeds = [b"central_authority"],
+        bump
+        )]
+    pub central_authority: Box<Account<'info, Data>>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub minting_signer: Signer<'info>,
+
+    pub renter: Signer<'info>,
+
+    #[account(mut,
+        associated_token::mint = mint,
+        associated_token::authority = renter
+    )]
+    pub renter_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut,
+        has_one = renter,
+        has_one = renter_ata,
+        has_one = rental_merkle_tree,
+    )]
+    pub rent_escrow: Box<Account<'info, RentalEscrow>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = rent_escrow,
+    )]
+    pub rent_escrow_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = minting_signer,
+        space = DayBookings::MAX_SIZE,
+        seeds = [
+            b"bookings",
+            rent_escrow.land_asset_id.as_ref(),
+            &DayBookings::seed_day(rent_escrow.end_time, 0),
+        ],
+        bump
+    )]
+    pub day_bookings: Box<Account<'info, DayBookings>>,
+
+    /// Only required when the extension continues past midnight
+    #[account(
+        init_if_needed,
+        payer = minting_signer,
+        space = DayBookings::MAX_SIZE,
+        seeds = [
+            b"bookings",
+            rent_escrow.land_asset_id.as_ref(),
+            &DayBookings::seed_day(rent_escrow.end_time, 1),
+        ],
+        bump
+    )]
+    pub next_day_bookings: Option<Box<Account<'info, DayBookings>>>,
+
+    /// CHECK: may not be initialized, the price is only read when the land owner has set one
+    #[account(
+        seeds = [b"land_pricing", rent_escrow.land_asset_id.as_ref()],
+        bump
+    )]
+    pub land_pricing: UncheckedAccount<'info>,
+
+    /// CHECK: may not be initialized, the land is always available unless its owner restricted it
+    #[account(
+        seeds = [b"land_availability", rent_escrow.land_asset_id.as_ref()],
+        bump
+    )]
+    pub land_availability: UncheckedAccount<'info>,
+
+    /// CHECK: may not be initialized, the land is active unless suspended by an admin
+    #[account(
+        seeds = [b"land_status", rent_escrow.land_asset_id.as_ref()],
+        bump
+    )]
+    pub land_status: UncheckedAccount<'info>,
+
+    /// CHECK: This account is checked in the instruction
+    pub land_merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: checked at ix
+    pub land_owner: UncheckedAccount<'info>,
+
+    /// CHECK: checked at ix
+    pub land_delegate: UncheckedAccount<'info>,
+
+    /// CHECK: delegate of the rental cNFT, checked by bubblegum
+    pub rental_delegate: UncheckedAccount<'info>,
+
+    /// CHECK: checked by bubblegum
+    #[account(mut)]
+    pub rental_merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: checked by bubblegum
+    pub tree_config: UncheckedAccount<'info>,
+
+    /// CHECK: checked by bubblegum
+    pub collection_mint: UncheckedAccount<'info>,
+
+    /// CHECK: checked by bubblegum
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    pub bubblegum_program: Program<'info, MplBubblegumProgramAccount>,
+    pub log_wrapper: Program<'info, NoopProgramAccount>,
+    pub compression_program: Program<'info, SplAccountCompressionProgramAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Books `slot_count` more slots right after the end of a rental that hasn't ended yet, and
+/// adds their cost to the same escrow so a single settlement pays the whole rental.
+///
+/// `remaining_accounts` holds the `land_proof_len` proof nodes of the land leaf, followed by
+/// the proof of the rental leaf when `metadata_update` is provided.
+pub fn handle_extend_rental<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExtendRentalAccounts<'info>>,
+    slot_count: u8,
+    land_asset_id_leaf_data: LeafData,
+    land_proof_len: u8,
+    metadata_update: Option<RentalMetadataUpdate>,
+) -> Result<()> {
+    let central_authority = &ctx.accounts.central_authority;
+    central_authority.check_minting_signer(ctx.accounts.minting_signer.key())?;
+
+    if central_authority.rentals_paused {
+        return err!(CustomErrors::RentalsPaused);
+    }
+    if LandStatus::is_suspended(&ctx.accounts.land_status)? {
+        return err!(CustomErrors::LandSuspended);
+    }
+
+    if ctx.accounts.mint.key() != central_authority.mint_address {
+        return err!(CustomErrors::InvalidMint);
+    }
+
+    let escrow = &ctx.accounts.rent_escrow;
+    if escrow.is_pending() {
+        return err!(CustomErrors::RentalPending);
+    }
+    if escrow.disputed {
+        return err!(CustomErrors::RentalDisputed);
+    }
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    if current_timestamp >= escrow.end_time {
+        return err!(CustomErrors::RentalEnded);
+    }
+
+    // Extensions keep the slot length the rental was booked with, and the whole rental still
+    // spans at most a full day
+    let slot_seconds = escrow.slot_seconds;
+    let start_time = escrow.end_time;
+    let end_time = start_time + slot_count as i64 * slot_seconds;
+    if slot_count == 0 || end_time - escrow.start_time > SECONDS_PER_DAY {
+        return err!(CustomErrors::InvalidSlotCount);
+    }
+
+    let range_offset = (slot_count as i64 - 1) * slot_seconds;
+    if start_time > central_authority.max_advance_seconds + current_timestamp - range_offset {
+        return err!(CustomErrors::TimeToFarInFuture);
+    }
+
+    let land_proof_len = land_proof_len as usize;
+    if ctx.remaining_accounts.len() < land_proof_len {
+        return err!(CustomErrors::InvalidRemainingAccountsPassed);
+    }
+    let (land_proof, rental_proof) = ctx.remaining_accounts.split_at(land_proof_len);
+
+    let asset_id = get_asset_id(
+        &ctx.accounts.land_merkle_tree.key(),
+        land_asset_id_leaf_data.nonce,
+    );
+    require_keys_eq!(escrow.land_asset_id, asset_id);
+
+    central_authority.check_received_creator_hash(&land_asset_id_leaf_data.creator_hash)?;
+    let land_proof: Vec<_> = land_proof
+        .iter()
+        .map(|account| (account, false, false))
+        .collect();
+    land_asset_id_leaf_data.verify_leaf(
+        &ctx.accounts.compression_program.to_account_info(),
+        &ctx.accounts.land_merkle_tree.to_account_info(),
+        escrow.land_asset_id,
+        ctx.accounts.land_owner.key(),
+        ctx.accounts.land_delegate.key(),
+        &land_proof,
+    )?;
+
+    if !LandAvailability::is_available(
+        &ctx.accounts.land_availability,
+        ctx.accounts.land_owner.key(),
+        start_time,
+        end_time,
+    )? {
+        return err!(CustomErrors::LandUnavailable);
+    }
+
+    // Land owner pricing takes precedence over the default `base_cost`
+    let slot_price = LandPricing::price_for(
+        &ctx.accounts.land_pricing.to_account_info(),
+        ctx.accounts.land_owner.key(),
+    )?
+    .unwrap_or(central_authority.base_cost);
+    let additional_cost =
+        central_authority.range_price(slot_price, start_time, slot_seconds, slot_count as i64)?;
+    let additional_fee = bps_share(additional_cost, central_authority.admin_quota_bps)?;
+
+    let land_asset_id = escrow.land_asset_id;
+    book_slots(
+        &mut ctx.accounts.day_bookings,
+        ctx.accounts
+            .next_day_bookings
+            .as_deref_mut()
+            .map(|bookings| &mut **bookings),
+        land_asset_id,
+        start_time,
+        end_time,
+    )?;
+
+    transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.renter_ata.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.rent_escrow_ata.to_account_info(),
+                authority: ctx.accounts.renter.to_account_info(),
+            },
+        ),
+        additional_cost,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    if let Some(metadata_update) = metadata_update {
+        let rental_leaf_data = metadata_update.rental_leaf_data;
+        let asset_id = get_asset_id(
+            &ctx.accounts.rental_merkle_tree.key(),
+            rental_leaf_data.nonce,
+        );
+        if asset_id != ctx.accounts.rent_escrow.rental_asset_id {
+            return err!(CustomErrors::InvalidRentalAddressPassed);
+        }
+
+        let rental_proof: Vec<_> = rental_proof
+            .iter()
+            .map(|account| (account, false, false))
+            .collect();
+        UpdateMetadataCpiBuilder::new(&ctx.accounts.bubblegum_program.to_account_info())
+            .tree_config(&ctx.accounts.tree_config.to_account_info())
+            .authority(&ctx.accounts.minting_signer.to_account_info())
+            .collection_mint(Some(&ctx.accounts.collection_mint.to_account_info()))
+            .collection_metadata(Some(&ctx.accounts.collection_metadata.to_account_info()))
+            .leaf_owner(&ctx.accounts.renter.to_account_info())
+            .leaf_delegate(&ctx.accounts.rental_delegate.to_account_info())
+            .payer(&ctx.accounts.minting_signer.to_account_info())
+            .merkle_tree(&ctx.accounts.rental_merkle_tree.to_account_info())
+            .log_wrapper(&ctx.accounts.log_wrapper.to_account_info())
+            .compression_program(&ctx.accounts.compression_program.to_account_info())
+            .token_metadata_program(&ctx.accounts.token_metadata_program.to_account_info())
+            .system_program(&ctx.accounts.system_program.to_account_info())
+            .root(rental_leaf_data.root)
+            .nonce(rental_leaf_data.nonce)
+            .index(rental_leaf_data.index)
+            .current_metadata(MetadataArgs::try_from_slice(
+                &metadata_update.current_metadata_args,
+            )?)
+            .update_args(UpdateArgs::try_from_slice(&metadata_update.update_args)?)
+            .add_remaining_accounts(&rental_proof)
+            .invoke()?;
+    }
+
+    let escrow = &mut ctx.accounts.rent_escrow;
+    escrow.end_time = end_time;
+    escrow.expected_cost = escrow
+        .expected_cost
+        .checked_add(additional_cost)
+        .ok_or(CustomErrors::MathOverflow)?;
+    escrow.fee_quota = escrow
+        .fee_quota
+        .checked_add(additional_fee)
+        .ok_or(CustomErrors::MathOverflow)?;
+
+    emit_cpi!(RentalExtended {
+        land_asset_id,
+        rental_asset_id: escrow.rental_asset_id,
+        rent_escrow: escrow.key(),
+        renter: escrow.renter,
+        end_time,
+        additional_cost,
+        additional_fee,
+    });
+
+    Ok(())
+}
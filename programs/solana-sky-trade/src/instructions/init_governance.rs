@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::*, state::*};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitGovernancePayload<'info> {
+    #[account(
+        mut,
+        seeds = [b"central_authority"],
+        bump
+        )]
+    pub central_authority: Box<Account<'info, Data>>,
+
+    #[account(
+        init,
+        payer = centralized_account,
+        space = Governance::MAX_SIZE,
+        seeds = [b"governance"],
+        bump
+    )]
+    pub governance: Box<Account<'info, Governance>>,
+
+    #[account(mut)]
+    pub centralized_account: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Hands config changes over to an M-of-N admin set. From then on `update_config` is disabled
+/// and changes go through `propose_config`, `approve_config` and `execute_config`. The
+/// `centralized_account` can't be handed off anymore either.
+pub fn handle_init_governance(
+    ctx: Context<InitGovernancePayload>,
+    admins: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    ctx.accounts
+        .central_authority
+        .check_authority(ctx.accounts.centralized_account.key())?;
+    Governance::check_settings(&admins, threshold)?;
+
+    let governance = &mut ctx.accounts.governance;
+    governance.admins = admins.clone();
+    governance.threshold = threshold;
+    governance.proposal_count = 0;
+    governance.bump = ctx.bumps.governance;
+
+    ctx.accounts.central_authority.config_governed = true;
+
+    emit_cpi!(GovernanceInitialized { admins, threshold });
+
+    Ok(())
+}
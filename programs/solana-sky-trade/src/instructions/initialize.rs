@@ -2,15 +2,17 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::Mint;
 
 use crate::errors::*;
+use crate::events::*;
 
 use crate::state::*;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct InitializePayload<'info> {
     #[account(
         init_if_needed,
         payer = payer,
-        space = 8 + Data::MAX_SIZE,
+        space = Data::ACCOUNT_SIZE,
         seeds = [b"central_authority"],
         bump
         )]
@@ -45,14 +47,18 @@ pub fn handle_initialize(ctx: Context<InitializePayload>) -> Result<()> {
     let data = &mut ctx.accounts.central_authority;
 
     // Account That Signs Every Single Tx
+    data.version = Data::VERSION;
     data.centralized_account = ctx.accounts.payer.key();
     data.initialized = true;
+    data.roles = AdminRoles::all(data.centralized_account);
 
     // 1 USDC
-    data.base_cost = 1 * u64::pow(10, ctx.accounts.mint_account.decimals as u32);
+    data.base_cost = 10u64
+        .checked_pow(ctx.accounts.mint_account.decimals as u32)
+        .ok_or(CustomErrors::MathOverflow)?;
 
     // Admin Quota: 30%
-    data.admin_quota = 0.3;
+    data.admin_quota_bps = 3_000;
 
     // Auction House program ID
     data.auction_house_address = ctx.accounts.auction_house_address.key();
@@ -67,5 +73,26 @@ pub fn handle_initialize(ctx: Context<InitializePayload>) -> Result<()> {
         verification_creator: ctx.accounts.verification_creator.key(),
     };
 
+    data.cancellation_tiers = Data::default_cancellation_tiers();
+
+    // Flat pricing until a schedule is configured
+    data.hour_multipliers_bps = [BPS_DENOMINATOR as u16; 24];
+    data.weekday_multipliers_bps = [BPS_DENOMINATOR as u16; 7];
+
+    // 30 minutes slots, bookable up to 3 months ahead
+    data.slot_minutes = Data::DEFAULT_SLOT_MINUTES;
+    data.max_advance_seconds = Data::DEFAULT_MAX_ADVANCE_SECONDS;
+
+    data.validate()?;
+    data.check_mint_account(&ctx.accounts.mint_account)?;
+
+    emit_cpi!(ConfigInitialized {
+        centralized_account: data.centralized_account,
+        fee_account: data.fee_account,
+        mint_address: data.mint_address,
+        base_cost: data.base_cost,
+        admin_quota_bps: data.admin_quota_bps,
+    });
+
     Ok(())
 }
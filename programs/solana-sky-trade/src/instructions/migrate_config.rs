@@ -0,0 +1,293 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+    Discriminator,
+};
+
+use crate::{errors::*, events::*, state::*};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateConfigPayload<'info> {
+    /// CHECK: still in the legacy layout, checked in the instruction
+    #[account(
+        mut,
+        seeds = [b"central_authority"],
+        bump
+        )]
+    pub central_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub centralized_account: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Upgrades the `Data` account from the legacy layout, where `admin_quota` was an `f64`, to the
+/// current one, filling the settings introduced since then with their defaults.
+///
+/// The legacy layout is unversioned, so it is recognized by its size.
+///
+/// `remaining_accounts` lists every legacy `RentEscrow` still open, so they are counted in
+/// `Data::open_escrows` and the mint can't change before they are settled.
+pub fn handle_migrate_config(ctx: Context<MigrateConfigPayload>) -> Result<()> {
+    let central_authority = ctx.accounts.central_authority.to_account_info();
+
+    if central_authority.owner != &crate::ID {
+        return err!(ErrorCode::AccountOwnedByWrongProgram);
+    }
+
+    let mut migrated = migrated_config(&central_authority.try_borrow_data()?)?;
+    migrated.open_escrows = count_legacy_escrows(ctx.remaining_accounts)?;
+
+    if migrated.centralized_account != ctx.accounts.centralized_account.key() {
+        return err!(CustomErrors::InvalidAuthority);
+    }
+
+    let new_size = Data::ACCOUNT_SIZE;
+    let missing_lamports = Rent::get()?
+        .minimum_balance(new_size)
+        .saturating_sub(central_authority.lamports());
+    if missing_lamports > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.centralized_account.to_account_info(),
+                    to: central_authority.clone(),
+                },
+            ),
+            missing_lamports,
+        )?;
+    }
+    central_authority.realloc(new_size, true)?;
+
+    let mut data = central_authority.try_borrow_mut_data()?;
+    migrated.try_serialize(&mut &mut data[..])?;
+
+    emit_cpi!(ConfigMigrated {
+        centralized_account: migrated.centralized_account,
+        open_escrows: migrated.open_escrows,
+    });
+
+    Ok(())
+}
+
+/// Counts the legacy escrows passed, each has to be a distinct `RentEscrow` of the program.
+fn count_legacy_escrows(escrows: &[AccountInfo]) -> Result<u64> {
+    for (index, escrow) in escrows.iter().enumerate() {
+        if escrow.owner != &crate::ID {
+            return err!(ErrorCode::AccountOwnedByWrongProgram);
+        }
+        let data = escrow.try_borrow_data()?;
+        if data.len() < 8 || data[..8] != RentEscrow::DISCRIMINATOR {
+            return err!(ErrorCode::AccountDiscriminatorMismatch);
+        }
+        if escrows[..index].iter().any(|other| other.key == escrow.key) {
+            return err!(CustomErrors::DuplicateLegacyEscrow);
+        }
+    }
+    Ok(escrows.len() as u64)
+}
+
+/// Reads the `Data` account left in the legacy layout and upgrades it to the current one.
+fn migrated_config(data: &[u8]) -> Result<Data> {
+    if data.len() <= 8 || data[..8] != Data::DISCRIMINATOR {
+        return err!(ErrorCode::AccountDiscriminatorMismatch);
+    }
+
+    if data.len() != LegacyData::ACCOUNT_SIZE {
+        return if data[8] == Data::VERSION {
+            err!(CustomErrors::ConfigAlreadyMigrated)
+        } else {
+            err!(CustomErrors::UnsupportedConfigVersion)
+        };
+    }
+
+    let legacy = LegacyData::deserialize(&mut &data[8..])?;
+    Ok(Data {
+        version: Data::VERSION,
+        initialized: legacy.initialized,
+        centralized_account: legacy.centralized_account,
+        base_cost: legacy.base_cost,
+        admin_quota_bps: legacy.admin_quota_bps(),
+        auction_house_address: legacy.auction_house_address,
+        fee_account: legacy.fee_account,
+        mint_address: legacy.mint_address,
+        land_creators: legacy.land_creators,
+        cancellation_tiers: Data::default_cancellation_tiers(),
+        hour_multipliers_bps: [BPS_DENOMINATOR as u16; 24],
+        weekday_multipliers_bps: [BPS_DENOMINATOR as u16; 7],
+        keeper_reward_bps: 0,
+        rent_to_keeper: false,
+        dispute_window_seconds: 0,
+        arbiter: Pubkey::default(),
+        pending_authority: Pubkey::default(),
+        pending_authority_eta: 0,
+        authority_timelock_seconds: 0,
+        config_governed: false,
+        roles: AdminRoles::all(legacy.centralized_account),
+        rentals_paused: false,
+        settlements_paused: false,
+        slot_minutes: Data::DEFAULT_SLOT_MINUTES,
+        max_advance_seconds: Data::DEFAULT_MAX_ADVANCE_SECONDS,
+        min_lead_seconds: 0,
+        running_slot_bookable: false,
+        resale_royalty_bps: 0,
+        open_escrows: 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn creators(key: Pubkey) -> Creators {
+        Creators {
+            royalties_receiver: key,
+            mint_creator: key,
+            verification_creator: key,
+        }
+    }
+
+    fn account_data(fields: &impl AnchorSerialize, size: usize) -> Vec<u8> {
+        let mut data = Data::DISCRIMINATOR.to_vec();
+        fields.serialize(&mut data).unwrap();
+        data.resize(size.max(data.len()), 0);
+        data
+    }
+
+    #[test]
+    fn legacy_config_is_upgraded_to_the_current_layout() {
+        let authority = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let legacy = LegacyData {
+            initialized: true,
+            centralized_account: authority,
+            base_cost: 2_000_000,
+            admin_quota: 0.3,
+            auction_house_address: Pubkey::new_unique(),
+            fee_account: Pubkey::new_unique(),
+            mint_address: mint,
+            land_creators: creators(Pubkey::new_unique()),
+        };
+
+        let data = migrated_config(&account_data(&legacy, LegacyData::ACCOUNT_SIZE)).unwrap();
+
+        assert_eq!(data.version, Data::VERSION);
+        assert!(data.initialized);
+        assert_eq!(data.centralized_account, authority);
+        assert_eq!(data.base_cost, 2_000_000);
+        assert_eq!(data.admin_quota_bps, 3_000);
+        assert_eq!(data.auction_house_address, legacy.auction_house_address);
+        assert_eq!(data.fee_account, legacy.fee_account);
+        assert_eq!(data.mint_address, mint);
+        assert_eq!(
+            data.land_creators.royalties_receiver,
+            legacy.land_creators.royalties_receiver
+        );
+
+        // Settings introduced since then get their defaults
+        assert_eq!(
+            data.cancellation_tiers.len(),
+            Data::default_cancellation_tiers().len()
+        );
+        assert_eq!(data.hour_multipliers_bps, [BPS_DENOMINATOR as u16; 24]);
+        assert_eq!(data.weekday_multipliers_bps, [BPS_DENOMINATOR as u16; 7]);
+        assert_eq!(data.roles.pricing_admin, authority);
+        assert_eq!(data.roles.fee_admin, authority);
+        assert_eq!(data.roles.minting_signer, authority);
+        assert_eq!(data.roles.pause_guardian, authority);
+        assert!(!data.config_governed);
+        assert!(!data.rentals_paused && !data.settlements_paused);
+        assert_eq!(data.slot_minutes, Data::DEFAULT_SLOT_MINUTES);
+        assert_eq!(data.max_advance_seconds, Data::DEFAULT_MAX_ADVANCE_SECONDS);
+        assert!(!data.running_slot_bookable);
+        assert_eq!(data.resale_royalty_bps, 0);
+        assert_eq!(data.open_escrows, 0);
+        data.validate().unwrap();
+
+        // The migrated account fits the space allocated for it
+        let mut serialized = Vec::new();
+        data.try_serialize(&mut serialized).unwrap();
+        assert!(serialized.len() <= Data::ACCOUNT_SIZE);
+    }
+
+    #[test]
+    fn open_legacy_escrows_are_counted_once() {
+        let keys = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut lamports = [0; 3];
+        let mut data = [
+            RentEscrow::DISCRIMINATOR.to_vec(),
+            RentEscrow::DISCRIMINATOR.to_vec(),
+            RentalEscrow::DISCRIMINATOR.to_vec(),
+        ];
+        let [first_lamports, second_lamports, other_lamports] = &mut lamports;
+        let [first_data, second_data, other_data] = &mut data;
+        let first = AccountInfo::new(
+            &keys[0],
+            false,
+            true,
+            first_lamports,
+            first_data,
+            &crate::ID,
+            false,
+            0,
+        );
+        let second = AccountInfo::new(
+            &keys[1],
+            false,
+            true,
+            second_lamports,
+            second_data,
+            &crate::ID,
+            false,
+            0,
+        );
+        let current = AccountInfo::new(
+            &keys[1],
+            false,
+            true,
+            other_lamports,
+            other_data,
+            &crate::ID,
+            false,
+            0,
+        );
+
+        assert_eq!(count_legacy_escrows(&[]), Ok(0));
+        assert_eq!(
+            count_legacy_escrows(&[first.clone(), second.clone()]),
+            Ok(2)
+        );
+        assert_eq!(
+            count_legacy_escrows(&[first.clone(), second, first.clone()]),
+            err!(CustomErrors::DuplicateLegacyEscrow)
+        );
+        assert_eq!(
+            count_legacy_escrows(&[first, current]),
+            err!(ErrorCode::AccountDiscriminatorMismatch)
+        );
+    }
+
+    #[test]
+    fn current_or_unknown_layouts_are_refused() {
+        let mut data = account_data(&[Data::VERSION, 1], 0);
+        assert_eq!(
+            migrated_config(&data).err(),
+            Some(error!(CustomErrors::ConfigAlreadyMigrated))
+        );
+
+        data[8] = Data::VERSION + 1;
+        assert_eq!(
+            migrated_config(&data).err(),
+            Some(error!(CustomErrors::UnsupportedConfigVersion))
+        );
+
+        data[0] ^= 1;
+        assert_eq!(
+            migrated_config(&data).err(),
+            Some(error!(ErrorCode::AccountDiscriminatorMismatch))
+        );
+    }
+}
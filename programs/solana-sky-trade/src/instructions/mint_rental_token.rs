@@ -3,17 +3,15 @@ use anchor_spl::{
     associated_token::AssociatedToken,
     token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
 };
-use chrono::*;
 use mpl_bubblegum::{
-    instructions::{
-        MintToCollectionV1CpiBuilder, VerifyLeafCpi, VerifyLeafCpiAccounts,
-        VerifyLeafInstructionArgs,
-    },
-    types::{LeafSchema, MetadataArgs},
+    accounts::TreeConfig,
+    instructions::MintToCollectionV1CpiBuilder,
+    types::MetadataArgs,
+    utils::get_asset_id,
 };
 use mpl_token_metadata::ID;
 
-use crate::{state::*, CustomErrors};
+use crate::{events::*, state::*, CustomErrors};
 
 #[derive(Clone)]
 pub struct Metadata;
@@ -24,10 +22,12 @@ impl anchor_lang::Id for Metadata {
     }
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-#[instruction(land_asset_id:Pubkey,creation_time:String)]
+#[instruction(land_asset_id:Pubkey,start_time:i64)]
 pub struct MintRentalTokenPayload<'info> {
     #[account(
+        mut,
         seeds = [b"central_authority"],
         bump
         )]
@@ -36,7 +36,7 @@ pub struct MintRentalTokenPayload<'info> {
     pub mint: Box<Account<'info, Mint>>,
 
     #[account(mut)]
-    pub centralized_account: Signer<'info>,
+    pub minting_signer: Signer<'info>,
 
     #[account(mut)]
     pub caller: Signer<'info>,
@@ -49,25 +49,80 @@ pub struct MintRentalTokenPayload<'info> {
 
     #[account(
         init,
-        payer=centralized_account,
-        space=RentEscrow::MAX_SIZE,
+        payer=minting_signer,
+        space=RentalEscrow::MAX_SIZE,
         seeds=[
-            b"escrow",
+            b"rental_escrow",
             land_asset_id.key().as_ref(),
-            creation_time.as_ref()
+            &start_time.div_euclid(60).to_le_bytes()
         ],
         bump
     )]
-    rent_escrow: Account<'info, RentEscrow>,
+    rent_escrow: Box<Account<'info, RentalEscrow>>,
 
     #[account(
         init,
-        payer=centralized_account,
+        payer=minting_signer,
         associated_token::mint = mint,
         associated_token::authority = rent_escrow,
         )]
     rent_escrow_ata: Box<Account<'info, TokenAccount>>,
 
+    #[account(
+        init_if_needed,
+        payer = minting_signer,
+        space = DayBookings::MAX_SIZE,
+        seeds = [
+            b"bookings",
+            land_asset_id.as_ref(),
+            &DayBookings::seed_day(start_time, 0),
+        ],
+        bump
+    )]
+    pub day_bookings: Box<Account<'info, DayBookings>>,
+
+    /// Only required when the rental continues past midnight
+    #[account(
+        init_if_needed,
+        payer = minting_signer,
+        space = DayBookings::MAX_SIZE,
+        seeds = [
+            b"bookings",
+            land_asset_id.as_ref(),
+            &DayBookings::seed_day(start_time, 1),
+        ],
+        bump
+    )]
+    pub next_day_bookings: Option<Box<Account<'info, DayBookings>>>,
+
+    /// CHECK: may not be initialized, the price is only read when the land owner has set one
+    #[account(
+        seeds = [b"land_pricing", land_asset_id.as_ref()],
+        bump
+    )]
+    pub land_pricing: UncheckedAccount<'info>,
+
+    /// CHECK: may not be initialized, the land is always available unless its owner restricted it
+    #[account(
+        seeds = [b"land_availability", land_asset_id.as_ref()],
+        bump
+    )]
+    pub land_availability: UncheckedAccount<'info>,
+
+    /// CHECK: may not be initialized, bookings are instant unless the land owner requires approval
+    #[account(
+        seeds = [b"land_booking_mode", land_asset_id.as_ref()],
+        bump
+    )]
+    pub land_booking_mode: UncheckedAccount<'info>,
+
+    /// CHECK: may not be initialized, the land is active unless suspended by an admin
+    #[account(
+        seeds = [b"land_status", land_asset_id.as_ref()],
+        bump
+    )]
+    pub land_status: UncheckedAccount<'info>,
+
     /// CHECK: This account is checked in the instruction
     #[account(mut)]
     pub rental_merkle_tree: AccountInfo<'info>,
@@ -108,38 +163,29 @@ pub struct MintRentalTokenPayload<'info> {
 }
 
 impl<'info> MintRentalTokenPayload<'info> {
-    /// Checks that the received creators matches the ones stored in the `Data` account
-    ///  and makes a CPI to verify that is also valid data in the merkle_tree
+    /// Checks that the land asset id belongs to the merkle_tree, that the received creators
+    /// matches the ones stored in the `Data` account and makes a CPI to verify that is also
+    /// valid data in the merkle_tree
     pub fn verify_land_asset_id_creators(
         &self,
         land_asset_id_leaf_data: LeafData,
         land_asset_id: Pubkey,
         proof: Vec<(&AccountInfo<'info>, bool, bool)>,
     ) -> Result<()> {
+        let asset_id = get_asset_id(&self.land_merkle_tree.key(), land_asset_id_leaf_data.nonce);
+        require_keys_eq!(land_asset_id, asset_id);
+
         self.central_authority
             .check_received_creator_hash(&land_asset_id_leaf_data.creator_hash)?;
 
-        let leaf = LeafSchema::V1 {
-            id: land_asset_id,
-            owner: self.land_owner.key(),
-            delegate: self.land_delegate.key(),
-            nonce: land_asset_id_leaf_data.nonce,
-            data_hash: land_asset_id_leaf_data.hash,
-            creator_hash: land_asset_id_leaf_data.creator_hash,
-        };
-
-        VerifyLeafCpi::new(
+        land_asset_id_leaf_data.verify_leaf(
             &self.compression_program.to_account_info(),
-            VerifyLeafCpiAccounts {
-                merkle_tree: &self.land_merkle_tree.to_account_info(),
-            },
-            VerifyLeafInstructionArgs {
-                index: land_asset_id_leaf_data.index,
-                root: land_asset_id_leaf_data.root,
-                leaf: leaf.hash(),
-            },
-        )
-        .invoke_with_remaining_accounts(&proof)?;
+            &self.land_merkle_tree.to_account_info(),
+            land_asset_id,
+            self.land_owner.key(),
+            self.land_delegate.key(),
+            &proof,
+        )?;
 
         Ok(())
     }
@@ -148,41 +194,95 @@ impl<'info> MintRentalTokenPayload<'info> {
 pub fn handle_mint_rental_token<'info>(
     ctx: Context<'_, '_, '_, 'info, MintRentalTokenPayload<'info>>,
     land_asset_id: Pubkey,
-    creation_time: String,
+    start_time: i64,
     bump: u8,
     mint_metadata_args: Vec<u8>,
     land_asset_id_leaf_data: LeafData,
+    max_cost: u64,
 ) -> Result<()> {
-    let rfc3339 = DateTime::parse_from_rfc3339(&creation_time);
-    match rfc3339 {
-        Ok(rfc3339) => {
-            msg!("rfc3339: {:?}", rfc3339);
-        }
-        Err(e) => {
-            msg!("Error parsing RFC3339 string: {:?}", e);
-            return err!(CustomErrors::InvalidTimeString);
-        }
+    handle_mint_rental_range(
+        ctx,
+        land_asset_id,
+        start_time,
+        1,
+        bump,
+        mint_metadata_args,
+        land_asset_id_leaf_data,
+        max_cost,
+    )
+}
+
+/// Books `slot_count` consecutive slots starting at the unix timestamp `start_time`, all held
+/// by a single escrow and a single rental cNFT.
+///
+/// The price can change between signing and execution, so the renter caps what they pay with
+/// `max_cost`, the admin fee included.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_mint_rental_range<'info>(
+    ctx: Context<'_, '_, '_, 'info, MintRentalTokenPayload<'info>>,
+    land_asset_id: Pubkey,
+    start_time: i64,
+    slot_count: u8,
+    bump: u8,
+    mint_metadata_args: Vec<u8>,
+    land_asset_id_leaf_data: LeafData,
+    max_cost: u64,
+) -> Result<()> {
+    let slot_seconds = ctx.accounts.central_authority.slot_seconds();
+
+    // A booking spans at most a full day, so it never touches more than two `DayBookings`
+    if slot_count == 0 || slot_count as i64 * slot_seconds > SECONDS_PER_DAY {
+        return err!(CustomErrors::InvalidSlotCount);
+    }
+
+    if start_time.rem_euclid(slot_seconds) != 0 {
+        msg!("start_time {}", start_time);
+        return err!(CustomErrors::InvalidTime);
+    }
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+
+    ctx.accounts
+        .central_authority
+        .check_minting_signer(ctx.accounts.minting_signer.key())?;
+
+    if ctx.accounts.central_authority.rentals_paused {
+        return err!(CustomErrors::RentalsPaused);
+    }
+    if LandStatus::is_suspended(&ctx.accounts.land_status)? {
+        return err!(CustomErrors::LandSuspended);
     }
-    let creation_second = rfc3339.unwrap().timestamp() as u64;
-    let time_limit = 3 * 30 * 24 * 60 * 60;
-    let current_timestamp = Clock::get().unwrap().unix_timestamp as u64;
 
     let mint_pubkey = ctx.accounts.mint.key();
     if mint_pubkey != ctx.accounts.central_authority.mint_address {
         return err!(CustomErrors::InvalidMint);
     }
 
-    if creation_second > (time_limit + current_timestamp) {
-        msg!("creation_second {}", creation_second);
+    let data = &ctx.accounts.central_authority;
+    if start_time < current_timestamp {
+        // Only the slot in progress may still be booked, at full price
+        let running_slot_bookable = data.running_slot_bookable
+            && data.min_lead_seconds == 0
+            && current_timestamp < start_time + slot_seconds;
+        if !running_slot_bookable {
+            msg!("start_time {}", start_time);
+            msg!("current_timestamp {}", current_timestamp);
+            return err!(CustomErrors::BookingInPast);
+        }
+    } else if start_time < current_timestamp + data.min_lead_seconds {
+        msg!("start_time {}", start_time);
         msg!("current_timestamp {}", current_timestamp);
-        msg!("time_limit {}", time_limit);
-        return err!(CustomErrors::TimeToFarInFuture);
+        return err!(CustomErrors::BookingTooSoon);
     }
 
-    let creation_min: u32 = rfc3339.unwrap().time().minute();
-    if creation_min != 0 && creation_min != 30 {
-        msg!("creation_min {}", creation_min);
-        return err!(CustomErrors::InvalidTime);
+    // Every slot of the range has to start within the booking window
+    let time_limit = ctx.accounts.central_authority.max_advance_seconds;
+    let range_offset = (slot_count as i64 - 1) * slot_seconds;
+    if start_time > time_limit + current_timestamp - range_offset {
+        msg!("start_time {}", start_time);
+        msg!("current_timestamp {}", current_timestamp);
+        msg!("time_limit {}", time_limit);
+        return err!(CustomErrors::TimeToFarInFuture);
     }
 
     // Verify land asset_id creators
@@ -194,28 +294,86 @@ pub fn handle_mint_rental_token<'info>(
     ctx.accounts
         .verify_land_asset_id_creators(land_asset_id_leaf_data, land_asset_id, proof)?;
 
-    let expiration_time: String = rfc3339
-        .unwrap()
-        .checked_add_signed(Duration::minutes(30))
-        .unwrap()
-        .format("%Y-%m-%dT%H:%M:%S%.3fZ")
-        .to_string();
+    let end_time = start_time + slot_count as i64 * slot_seconds;
 
-    let expected_cost = ctx.accounts.central_authority.base_cost  as u64;
+    if !LandAvailability::is_available(
+        &ctx.accounts.land_availability,
+        ctx.accounts.land_owner.key(),
+        start_time,
+        end_time,
+    )? {
+        return err!(CustomErrors::LandUnavailable);
+    }
+
+    // Land owner pricing takes precedence over the default `base_cost`
+    let slot_price = LandPricing::price_for(
+        &ctx.accounts.land_pricing.to_account_info(),
+        ctx.accounts.land_owner.key(),
+    )?
+    .unwrap_or(ctx.accounts.central_authority.base_cost);
+    let expected_cost = ctx.accounts.central_authority.range_price(
+        slot_price,
+        start_time,
+        slot_seconds,
+        slot_count as i64,
+    )?;
 
     let decimals = ctx.accounts.mint.decimals;
 
-    let fee_quota = ctx.accounts.central_authority.admin_quota * (expected_cost as f64);
-    let fee_quota = fee_quota as u64;
+    let fee_quota = bps_share(
+        expected_cost,
+        ctx.accounts.central_authority.admin_quota_bps,
+    )?;
 
-    let mint_metadata = MetadataArgs::try_from_slice(mint_metadata_args.as_slice())?;
+    // The fee is carved out of `expected_cost`, which is all the renter pays
+    if expected_cost > max_cost {
+        msg!("expected_cost {}", expected_cost);
+        msg!("max_cost {}", max_cost);
+        return err!(CustomErrors::CostAboveMaximum);
+    }
+
+    let approval_required = LandBookingMode::requires_approval(
+        &ctx.accounts.land_booking_mode,
+        ctx.accounts.land_owner.key(),
+    )?;
+
+    // The rental cNFT takes the next nonce of the rental tree
+    let tree_config = TreeConfig::try_from(&ctx.accounts.tree_config.to_account_info())?;
+    let rental_asset_id = get_asset_id(
+        &ctx.accounts.rental_merkle_tree.key(),
+        tree_config.num_minted,
+    );
+
+    book_slots(
+        &mut ctx.accounts.day_bookings,
+        ctx.accounts
+            .next_day_bookings
+            .as_deref_mut()
+            .map(|bookings| &mut **bookings),
+        land_asset_id,
+        start_time,
+        end_time,
+    )?;
 
     ctx.accounts.rent_escrow.land_asset_id = land_asset_id;
-    ctx.accounts.rent_escrow.creation_time = creation_time;
+    ctx.accounts.rent_escrow.start_time = start_time;
+    ctx.accounts.rent_escrow.end_time = end_time;
+    ctx.accounts.rent_escrow.slot_seconds = slot_seconds;
+    ctx.accounts.rent_escrow.slot_index_bytes = start_time.div_euclid(60).to_le_bytes();
     ctx.accounts.rent_escrow.escrow_bump = [bump];
     ctx.accounts.rent_escrow.expected_cost = expected_cost;
     ctx.accounts.rent_escrow.fee_quota = fee_quota;
-    ctx.accounts.rent_escrow.end_time = expiration_time;
+    ctx.accounts.rent_escrow.renter = ctx.accounts.caller.key();
+    ctx.accounts.rent_escrow.renter_ata = ctx.accounts.caller_ata.key();
+    ctx.accounts.rent_escrow.version = RentalEscrow::VERSION;
+    if approval_required {
+        ctx.accounts.rent_escrow.approval_deadline =
+            start_time.min(current_timestamp + APPROVAL_WINDOW_SECONDS);
+    } else {
+        ctx.accounts.rent_escrow.rental_asset_id = rental_asset_id;
+        ctx.accounts.rent_escrow.rental_merkle_tree = ctx.accounts.rental_merkle_tree.key();
+    }
+    ctx.accounts.central_authority.escrow_opened()?;
 
     transfer_checked(
         CpiContext::new(
@@ -231,14 +389,31 @@ pub fn handle_mint_rental_token<'info>(
         decimals,
     )?;
 
+    // Only the payment is locked until the land owner answers, `approve_rental` mints the
+    // rental cNFT
+    if approval_required {
+        emit_cpi!(RentalRequested {
+            land_asset_id,
+            rent_escrow: ctx.accounts.rent_escrow.key(),
+            renter: ctx.accounts.caller.key(),
+            start_time,
+            end_time,
+            expected_cost,
+            approval_deadline: ctx.accounts.rent_escrow.approval_deadline,
+        });
+        return Ok(());
+    }
+
+    let mint_metadata = MetadataArgs::try_from_slice(mint_metadata_args.as_slice())?;
+
     MintToCollectionV1CpiBuilder::new(&ctx.accounts.bubblegum_program.to_account_info())
         .tree_config(&ctx.accounts.tree_config.to_account_info())
         .leaf_owner(&ctx.accounts.caller.to_account_info())
-        .leaf_delegate(&ctx.accounts.centralized_account.to_account_info())
+        .leaf_delegate(&ctx.accounts.minting_signer.to_account_info())
         .merkle_tree(&ctx.accounts.rental_merkle_tree.to_account_info())
-        .payer(&ctx.accounts.centralized_account.to_account_info())
-        .tree_creator_or_delegate(&ctx.accounts.centralized_account.to_account_info())
-        .collection_authority(&ctx.accounts.centralized_account.to_account_info())
+        .payer(&ctx.accounts.minting_signer.to_account_info())
+        .tree_creator_or_delegate(&ctx.accounts.minting_signer.to_account_info())
+        .collection_authority(&ctx.accounts.minting_signer.to_account_info())
         .collection_mint(&ctx.accounts.collection_mint.to_account_info())
         .collection_metadata(&ctx.accounts.collection_metadata.to_account_info())
         .collection_edition(&ctx.accounts.collection_edition.to_account_info())
@@ -251,5 +426,16 @@ pub fn handle_mint_rental_token<'info>(
         .invoke()?;
     // .invoke_signed(signer_seeds)?;
 
+    emit_cpi!(RentalMinted {
+        land_asset_id,
+        rental_asset_id,
+        rent_escrow: ctx.accounts.rent_escrow.key(),
+        renter: ctx.accounts.caller.key(),
+        start_time,
+        end_time,
+        expected_cost,
+        fee_quota,
+    });
+
     Ok(())
 }
@@ -1,9 +1,67 @@
+pub mod accept_authority;
+pub mod approve_config;
+pub mod approve_rental;
+pub mod backfill_legacy_booking;
+pub mod cancel_authority_proposal;
+pub mod cancel_config;
+pub mod cancel_rental;
+pub mod close_day_bookings;
+pub mod end_rental_early;
+pub mod execute_config;
+pub mod expire_rental_request;
+pub mod extend_rental;
+pub mod init_governance;
 pub mod initialize;
+pub mod migrate_config;
 pub mod mint_rental_token;
+pub mod open_dispute;
+pub mod propose_authority;
+pub mod propose_config;
+pub mod reject_rental;
+pub mod resolve_dispute;
 pub mod transfer_on_expiry;
+pub mod transfer_rental;
+pub mod set_land_availability;
+pub mod set_land_booking_mode;
+pub mod set_land_pricing;
+pub mod set_land_status;
+pub mod set_pause;
+pub mod settle_batch;
 pub mod update_config;
+pub mod update_fees;
+pub mod update_pricing;
+pub mod void_rental;
 
+pub use accept_authority::*;
+pub use approve_config::*;
+pub use approve_rental::*;
+pub use backfill_legacy_booking::*;
+pub use cancel_authority_proposal::*;
+pub use cancel_config::*;
+pub use cancel_rental::*;
+pub use close_day_bookings::*;
+pub use end_rental_early::*;
+pub use execute_config::*;
+pub use expire_rental_request::*;
+pub use extend_rental::*;
+pub use init_governance::*;
 pub use initialize::*;
+pub use migrate_config::*;
 pub use mint_rental_token::*;
+pub use open_dispute::*;
+pub use propose_authority::*;
+pub use propose_config::*;
+pub use reject_rental::*;
+pub use resolve_dispute::*;
 pub use transfer_on_expiry::*;
+pub use transfer_rental::*;
+pub use set_land_availability::*;
+pub use set_land_booking_mode::*;
+pub use set_land_pricing::*;
+pub use set_land_status::*;
+pub use set_pause::*;
+pub use settle_batch::*;
 pub use update_config::*;
+pub use update_fees::*;
+pub use update_pricing::*;
+pub use void_rental::*;
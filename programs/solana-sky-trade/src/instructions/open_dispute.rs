@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::*, events::*, state::*};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct OpenDisputeAccounts<'info> {
+    #[account(
+        seeds = [b"central_authority"],
+        bump
+        )]
+    pub central_authority: Box<Account<'info, Data>>,
+
+    pub renter: Signer<'info>,
+
+    #[account(mut, has_one = renter)]
+    pub rent_escrow: Box<Account<'info, RentalEscrow>>,
+}
+
+/// Freezes the payout of a rental until `resolve_dispute` is called.
+///
+/// Only the renter can dispute, from the rental start until `Data::dispute_window_seconds`
+/// after it ends.
+pub fn handle_open_dispute(ctx: Context<OpenDisputeAccounts>) -> Result<()> {
+    let escrow = &mut ctx.accounts.rent_escrow;
+    if escrow.disputed {
+        return err!(CustomErrors::RentalDisputed);
+    }
+    if escrow.is_pending() {
+        return err!(CustomErrors::RentalPending);
+    }
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let settlement_time = ctx.accounts.central_authority.settlement_time(escrow.end_time);
+    if current_timestamp < escrow.start_time || current_timestamp >= settlement_time {
+        return err!(CustomErrors::DisputeWindowClosed);
+    }
+
+    escrow.disputed = true;
+
+    emit_cpi!(DisputeOpened {
+        land_asset_id: escrow.land_asset_id,
+        rent_escrow: escrow.key(),
+        renter: escrow.renter,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::*, events::*, state::*};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ProposeAuthorityPayload<'info> {
+    #[account(
+        mut,
+        seeds = [b"central_authority"],
+        bump
+        )]
+    pub central_authority: Box<Account<'info, Data>>,
+
+    pub centralized_account: Signer<'info>,
+}
+
+/// First step of replacing the `centralized_account`: records `new_authority` as pending. It only
+/// takes over once it accepts, after `Data::authority_timelock_seconds`.
+///
+/// A new proposal replaces the pending one. Once governance is set up the authority can't hand
+/// off on its own anymore.
+pub fn handle_propose_authority(
+    ctx: Context<ProposeAuthorityPayload>,
+    new_authority: Pubkey,
+) -> Result<()> {
+    let data = &mut ctx.accounts.central_authority;
+    data.check_authority(ctx.accounts.centralized_account.key())?;
+
+    if data.config_governed {
+        return err!(CustomErrors::AuthorityUnderGovernance);
+    }
+
+    if new_authority == Pubkey::default() {
+        return err!(CustomErrors::InvalidAuthority);
+    }
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    data.pending_authority = new_authority;
+    data.pending_authority_eta = current_timestamp
+        .checked_add(data.authority_timelock_seconds)
+        .ok_or(CustomErrors::MathOverflow)?;
+
+    emit_cpi!(AuthorityProposed {
+        current_authority: data.centralized_account,
+        pending_authority: new_authority,
+        eta: data.pending_authority_eta,
+    });
+
+    Ok(())
+}
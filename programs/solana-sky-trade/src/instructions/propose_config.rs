@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::*, state::*, UpdateConfigData, UpdateFeesData, UpdatePricingData};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ProposeConfigPayload<'info> {
+    #[account(
+        seeds = [b"central_authority"],
+        bump
+        )]
+    pub central_authority: Box<Account<'info, Data>>,
+
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance.bump
+    )]
+    pub governance: Box<Account<'info, Governance>>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = ConfigProposal::MAX_SIZE,
+        seeds = [b"config_proposal", governance.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Box<Account<'info, ConfigProposal>>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Queues a config change, approved by the proposing admin. Pricing and fee changes are
+/// proposed alongside, as their admins can't update them once governance is set up, and so are
+/// changes to the governance admins and threshold.
+pub fn handle_propose_config(
+    ctx: Context<ProposeConfigPayload>,
+    changes: UpdateConfigData,
+    pricing_changes: Option<UpdatePricingData>,
+    fee_changes: Option<UpdateFeesData>,
+    governance_changes: Option<GovernanceSettings>,
+) -> Result<()> {
+    let proposer = ctx.accounts.proposer.key();
+    ctx.accounts.governance.check_admin(proposer)?;
+
+    if let Some(settings) = &governance_changes {
+        Governance::check_settings(&settings.admins, settings.threshold)?;
+    }
+
+    let id = ctx.accounts.governance.proposal_count;
+    ctx.accounts.governance.proposal_count += 1;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.id = id;
+    proposal.proposer = proposer;
+    proposal.changes = changes;
+    proposal.approvals = vec![proposer];
+    proposal.bump = ctx.bumps.proposal;
+    proposal.pricing_changes = pricing_changes;
+    proposal.fee_changes = fee_changes;
+    proposal.governance_changes = governance_changes;
+    proposal.created_at = Clock::get()?.unix_timestamp;
+
+    // Reject changes that could never be applied
+    proposal.apply(&mut (**ctx.accounts.central_authority).clone())?;
+
+    emit_cpi!(ConfigProposed {
+        proposal: proposal.key(),
+        id,
+        proposer,
+    });
+
+    Ok(())
+}
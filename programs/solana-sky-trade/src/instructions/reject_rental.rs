@@ -0,0 +1,191 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, transfer, CloseAccount, Mint, Token, TokenAccount, Transfer},
+};
+use mpl_bubblegum::utils::get_asset_id;
+
+use crate::{errors::*, events::*, state::*};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RejectRentalAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"central_authority"],
+        bump
+        )]
+    pub central_authority: Box<Account<'info, Data>>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    pub land_owner: Signer<'info>,
+
+    /// CHECK: checked at ix
+    pub land_delegate: UncheckedAccount<'info>,
+
+    /// CHECK: This account is checked in the instruction
+    pub land_merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: checked against the escrow
+    pub renter: UncheckedAccount<'info>,
+
+    #[account(mut,
+        associated_token::mint = mint,
+        associated_token::authority = renter
+    )]
+    pub renter_ata: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: checked at ix
+    #[account(mut)]
+    pub fee_account: UncheckedAccount<'info>,
+
+    #[account(mut,
+        has_one = renter,
+        has_one = renter_ata,
+        close = fee_account,
+    )]
+    pub rent_escrow: Box<Account<'info, RentalEscrow>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = rent_escrow,
+    )]
+    pub rent_escrow_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"bookings",
+            rent_escrow.land_asset_id.as_ref(),
+            &DayBookings::seed_day(rent_escrow.start_time, 0),
+        ],
+        bump
+    )]
+    pub day_bookings: Box<Account<'info, DayBookings>>,
+
+    /// Only required when the rental continues past midnight
+    #[account(
+        mut,
+        seeds = [
+            b"bookings",
+            rent_escrow.land_asset_id.as_ref(),
+            &DayBookings::seed_day(rent_escrow.start_time, 1),
+        ],
+        bump
+    )]
+    pub next_day_bookings: Option<Box<Account<'info, DayBookings>>>,
+
+    pub compression_program: Program<'info, SplAccountCompressionProgramAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RejectRentalAccounts<'info> {
+    fn transfer_refund_ctx(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.rent_escrow_ata.to_account_info(),
+                to: self.renter_ata.to_account_info(),
+                authority: self.rent_escrow.to_account_info(),
+            },
+        )
+    }
+
+    fn close_ata_context(&self) -> CpiContext<'_, '_, '_, 'info, CloseAccount<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.rent_escrow_ata.to_account_info(),
+                destination: self.fee_account.to_account_info(),
+                authority: self.rent_escrow.to_account_info(),
+            },
+        )
+    }
+}
+
+/// Turns down a rental request, refunding the renter in full and freeing the booked slots.
+/// Only the current owner of the land cNFT can reject.
+pub fn handle_reject_rental<'info>(
+    ctx: Context<'_, '_, '_, 'info, RejectRentalAccounts<'info>>,
+    land_asset_id_leaf_data: LeafData,
+) -> Result<()> {
+    if ctx.accounts.fee_account.key() != ctx.accounts.central_authority.fee_account {
+        return err!(CustomErrors::InvalidReceiver);
+    }
+
+    if ctx.accounts.mint.key() != ctx.accounts.central_authority.mint_address {
+        return err!(CustomErrors::InvalidMint);
+    }
+
+    if !ctx.accounts.rent_escrow.is_pending() {
+        return err!(CustomErrors::RentalNotPending);
+    }
+
+    let asset_id = get_asset_id(
+        &ctx.accounts.land_merkle_tree.key(),
+        land_asset_id_leaf_data.nonce,
+    );
+    require_keys_eq!(ctx.accounts.rent_escrow.land_asset_id, asset_id);
+
+    ctx.accounts
+        .central_authority
+        .check_received_creator_hash(&land_asset_id_leaf_data.creator_hash)?;
+    let proof: Vec<_> = ctx
+        .remaining_accounts
+        .iter()
+        .map(|account| (account, false, false))
+        .collect();
+    land_asset_id_leaf_data.verify_leaf(
+        &ctx.accounts.compression_program.to_account_info(),
+        &ctx.accounts.land_merkle_tree.to_account_info(),
+        ctx.accounts.rent_escrow.land_asset_id,
+        ctx.accounts.land_owner.key(),
+        ctx.accounts.land_delegate.key(),
+        &proof,
+    )?;
+
+    release_slots(
+        &mut ctx.accounts.day_bookings,
+        ctx.accounts
+            .next_day_bookings
+            .as_deref_mut()
+            .map(|bookings| &mut **bookings),
+        ctx.accounts.rent_escrow.land_asset_id,
+        ctx.accounts.rent_escrow.start_time,
+        ctx.accounts.rent_escrow.end_time,
+    )?;
+
+    let escrow = &ctx.accounts.rent_escrow;
+    let refund = escrow.expected_cost;
+    msg!("refund {}", refund);
+
+    if refund > 0 {
+        transfer(
+            ctx.accounts
+                .transfer_refund_ctx()
+                .with_signer(&[&escrow.escrow_seeds()]),
+            refund,
+        )?;
+    }
+
+    token::close_account(
+        ctx.accounts
+            .close_ata_context()
+            .with_signer(&[&escrow.escrow_seeds()]),
+    )?;
+    ctx.accounts.central_authority.escrow_closed()?;
+
+    emit_cpi!(RentalRejected {
+        land_asset_id: escrow.land_asset_id,
+        rent_escrow: escrow.key(),
+        renter: escrow.renter,
+        refund,
+        expired: false,
+    });
+
+    Ok(())
+}
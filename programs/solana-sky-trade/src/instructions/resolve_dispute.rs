@@ -0,0 +1,213 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, transfer, CloseAccount, Mint, Token, TokenAccount, Transfer},
+};
+use mpl_bubblegum::utils::get_asset_id;
+
+use crate::{errors::*, events::*, state::*};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ResolveDisputeAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"central_authority"],
+        bump
+        )]
+    pub central_authority: Box<Account<'info, Data>>,
+
+    pub arbiter: Signer<'info>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: checked against the escrow
+    pub renter: UncheckedAccount<'info>,
+
+    #[account(mut,
+        associated_token::mint = mint,
+        associated_token::authority = renter
+    )]
+    pub renter_ata: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: checked at ix
+    #[account(mut)]
+    pub fee_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = fee_account,
+    )]
+    pub fee_account_ata: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: checked at ix
+    pub land_owner: UncheckedAccount<'info>,
+
+    /// CHECK: checked at ix
+    pub land_delegate: UncheckedAccount<'info>,
+
+    /// CHECK: checked at ix
+    pub payment_receiver: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = payment_receiver,
+    )]
+    pub payment_receiver_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut,
+        has_one = renter,
+        has_one = renter_ata,
+        close = fee_account,
+    )]
+    pub rent_escrow: Box<Account<'info, RentalEscrow>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = rent_escrow,
+    )]
+    pub rent_escrow_ata: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Don't need to check
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    pub compression_program: Program<'info, SplAccountCompressionProgramAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ResolveDisputeAccounts<'info> {
+    fn transfer_ctx(
+        &self,
+        to: AccountInfo<'info>,
+    ) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.rent_escrow_ata.to_account_info(),
+                to,
+                authority: self.rent_escrow.to_account_info(),
+            },
+        )
+    }
+
+    fn close_ata_context(&self) -> CpiContext<'_, '_, '_, 'info, CloseAccount<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.rent_escrow_ata.to_account_info(),
+                destination: self.fee_account.to_account_info(),
+                authority: self.rent_escrow.to_account_info(),
+            },
+        )
+    }
+}
+
+/// Splits the escrow of a disputed rental between the renter and the land owner.
+///
+/// The renter gets `renter_refund_bps` of the rental cost back, the fee is only taken on the
+/// part paid out to the land owner.
+pub fn handle_resolve_dispute<'info>(
+    ctx: Context<'_, '_, '_, 'info, ResolveDisputeAccounts<'info>>,
+    land_asset_id_leaf_data: LeafData,
+    renter_refund_bps: u16,
+) -> Result<()> {
+    ctx.accounts
+        .central_authority
+        .check_arbiter(ctx.accounts.arbiter.key())?;
+
+    if ctx.accounts.fee_account.key() != ctx.accounts.central_authority.fee_account {
+        return err!(CustomErrors::InvalidReceiver);
+    }
+
+    if ctx.accounts.mint.key() != ctx.accounts.central_authority.mint_address {
+        return err!(CustomErrors::InvalidMint);
+    }
+
+    let escrow = &ctx.accounts.rent_escrow;
+    if !escrow.disputed {
+        return err!(CustomErrors::RentalNotDisputed);
+    }
+
+    let owner_share_bps = (BPS_DENOMINATOR as u16)
+        .checked_sub(renter_refund_bps)
+        .ok_or(CustomErrors::BasisPointsOutOfRange)?;
+
+    let asset_id = get_asset_id(
+        &ctx.accounts.merkle_tree.key(),
+        land_asset_id_leaf_data.nonce,
+    );
+    require_keys_eq!(escrow.land_asset_id, asset_id);
+
+    ctx.accounts
+        .central_authority
+        .check_received_creator_hash(&land_asset_id_leaf_data.creator_hash)?;
+
+    //This checks land_owner as owner
+    land_asset_id_leaf_data.verify_leaf(
+        &ctx.accounts.compression_program.to_account_info(),
+        &ctx.accounts.merkle_tree.to_account_info(),
+        asset_id,
+        ctx.accounts.land_owner.key(),
+        ctx.accounts.land_delegate.key(),
+        ctx.remaining_accounts
+            .iter()
+            .map(|account| (account, false, false))
+            .collect::<Vec<_>>()
+            .as_slice(),
+    )?;
+
+    ctx.accounts.central_authority.check_payment_receiver(
+        &ctx.accounts.land_owner,
+        ctx.accounts.payment_receiver.key(),
+    )?;
+
+    let refund = bps_share(escrow.expected_cost, renter_refund_bps)?;
+    let fee = bps_share(escrow.fee_quota, owner_share_bps)?;
+    let owner_payment = escrow.expected_cost - refund - fee;
+
+    msg!("refund {}", refund);
+    msg!("fee {}", fee);
+    msg!("owner payment {}", owner_payment);
+
+    let payouts = [
+        (ctx.accounts.renter_ata.to_account_info(), refund),
+        (ctx.accounts.fee_account_ata.to_account_info(), fee),
+        (
+            ctx.accounts.payment_receiver_ata.to_account_info(),
+            owner_payment,
+        ),
+    ];
+    for (to, amount) in payouts {
+        if amount > 0 {
+            transfer(
+                ctx.accounts
+                    .transfer_ctx(to)
+                    .with_signer(&[&escrow.escrow_seeds()]),
+                amount,
+            )?;
+        }
+    }
+
+    token::close_account(
+        ctx.accounts
+            .close_ata_context()
+            .with_signer(&[&escrow.escrow_seeds()]),
+    )?;
+    ctx.accounts.central_authority.escrow_closed()?;
+
+    emit_cpi!(DisputeResolved {
+        land_asset_id: escrow.land_asset_id,
+        rent_escrow: escrow.key(),
+        arbiter: ctx.accounts.arbiter.key(),
+        refund,
+        fee,
+        owner_payment,
+    });
+
+    Ok(())
+}
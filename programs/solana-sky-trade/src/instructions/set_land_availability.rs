@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use mpl_bubblegum::utils::get_asset_id;
+
+use crate::{events::*, state::*};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(land_asset_id: Pubkey)]
+pub struct SetLandAvailabilityAccounts<'info> {
+    #[account(
+        seeds = [b"central_authority"],
+        bump
+        )]
+    pub central_authority: Box<Account<'info, Data>>,
+
+    #[account(mut)]
+    pub land_owner: Signer<'info>,
+
+    /// CHECK: checked at ix
+    pub land_delegate: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = land_owner,
+        space = LandAvailability::MAX_SIZE,
+        seeds = [b"land_availability", land_asset_id.as_ref()],
+        bump
+    )]
+    pub land_availability: Box<Account<'info, LandAvailability>>,
+
+    /// CHECK: This account is checked in the instruction
+    pub land_merkle_tree: UncheckedAccount<'info>,
+
+    pub compression_program: Program<'info, SplAccountCompressionProgramAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates or replaces the opening hours and blackouts of a land. Only the current owner of
+/// the land cNFT can set them.
+pub fn handle_set_land_availability<'info>(
+    ctx: Context<'_, '_, '_, 'info, SetLandAvailabilityAccounts<'info>>,
+    land_asset_id: Pubkey,
+    weekly_hours: [u32; 7],
+    blackouts: Vec<Blackout>,
+    land_asset_id_leaf_data: LeafData,
+) -> Result<()> {
+    LandAvailability::check_settings(&weekly_hours, &blackouts)?;
+
+    let asset_id = get_asset_id(
+        &ctx.accounts.land_merkle_tree.key(),
+        land_asset_id_leaf_data.nonce,
+    );
+    require_keys_eq!(land_asset_id, asset_id);
+
+    ctx.accounts
+        .central_authority
+        .check_received_creator_hash(&land_asset_id_leaf_data.creator_hash)?;
+
+    let proof: Vec<_> = ctx
+        .remaining_accounts
+        .iter()
+        .map(|account| (account, false, false))
+        .collect();
+    land_asset_id_leaf_data.verify_leaf(
+        &ctx.accounts.compression_program.to_account_info(),
+        &ctx.accounts.land_merkle_tree.to_account_info(),
+        land_asset_id,
+        ctx.accounts.land_owner.key(),
+        ctx.accounts.land_delegate.key(),
+        &proof,
+    )?;
+
+    let land_availability = &mut ctx.accounts.land_availability;
+    land_availability.land_asset_id = land_asset_id;
+    land_availability.owner = ctx.accounts.land_owner.key();
+    land_availability.weekly_hours = weekly_hours;
+    land_availability.blackouts = blackouts.clone();
+    land_availability.bump = ctx.bumps.land_availability;
+
+    emit_cpi!(LandAvailabilitySet {
+        land_asset_id,
+        owner: land_availability.owner,
+        weekly_hours,
+        blackouts,
+    });
+
+    Ok(())
+}
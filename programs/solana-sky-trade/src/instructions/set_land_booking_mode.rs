@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use mpl_bubblegum::utils::get_asset_id;
+
+use crate::{events::*, state::*};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(land_asset_id: Pubkey)]
+pub struct SetLandBookingModeAccounts<'info> {
+    #[account(
+        seeds = [b"central_authority"],
+        bump
+        )]
+    pub central_authority: Box<Account<'info, Data>>,
+
+    #[account(mut)]
+    pub land_owner: Signer<'info>,
+
+    /// CHECK: checked at ix
+    pub land_delegate: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = land_owner,
+        space = LandBookingMode::MAX_SIZE,
+        seeds = [b"land_booking_mode", land_asset_id.as_ref()],
+        bump
+    )]
+    pub land_booking_mode: Account<'info, LandBookingMode>,
+
+    /// CHECK: This account is checked in the instruction
+    pub land_merkle_tree: UncheckedAccount<'info>,
+
+    pub compression_program: Program<'info, SplAccountCompressionProgramAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Switches a land between instant bookings and bookings approved by the land owner. Only the
+/// current owner of the land cNFT can set it.
+pub fn handle_set_land_booking_mode<'info>(
+    ctx: Context<'_, '_, '_, 'info, SetLandBookingModeAccounts<'info>>,
+    land_asset_id: Pubkey,
+    approval_required: bool,
+    land_asset_id_leaf_data: LeafData,
+) -> Result<()> {
+    let asset_id = get_asset_id(
+        &ctx.accounts.land_merkle_tree.key(),
+        land_asset_id_leaf_data.nonce,
+    );
+    require_keys_eq!(land_asset_id, asset_id);
+
+    ctx.accounts
+        .central_authority
+        .check_received_creator_hash(&land_asset_id_leaf_data.creator_hash)?;
+
+    let proof: Vec<_> = ctx
+        .remaining_accounts
+        .iter()
+        .map(|account| (account, false, false))
+        .collect();
+    land_asset_id_leaf_data.verify_leaf(
+        &ctx.accounts.compression_program.to_account_info(),
+        &ctx.accounts.land_merkle_tree.to_account_info(),
+        land_asset_id,
+        ctx.accounts.land_owner.key(),
+        ctx.accounts.land_delegate.key(),
+        &proof,
+    )?;
+
+    let land_booking_mode = &mut ctx.accounts.land_booking_mode;
+    land_booking_mode.land_asset_id = land_asset_id;
+    land_booking_mode.owner = ctx.accounts.land_owner.key();
+    land_booking_mode.approval_required = approval_required;
+    land_booking_mode.bump = ctx.bumps.land_booking_mode;
+
+    emit_cpi!(LandBookingModeSet {
+        land_asset_id,
+        owner: land_booking_mode.owner,
+        approval_required,
+    });
+
+    Ok(())
+}
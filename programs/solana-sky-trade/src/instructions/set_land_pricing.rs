@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use mpl_bubblegum::utils::get_asset_id;
+
+use crate::{errors::*, events::*, state::*};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(land_asset_id: Pubkey)]
+pub struct SetLandPricingAccounts<'info> {
+    #[account(
+        seeds = [b"central_authority"],
+        bump
+        )]
+    pub central_authority: Box<Account<'info, Data>>,
+
+    #[account(mut)]
+    pub land_owner: Signer<'info>,
+
+    /// CHECK: checked at ix
+    pub land_delegate: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = land_owner,
+        space = LandPricing::MAX_SIZE,
+        seeds = [b"land_pricing", land_asset_id.as_ref()],
+        bump
+    )]
+    pub land_pricing: Account<'info, LandPricing>,
+
+    /// CHECK: This account is checked in the instruction
+    pub land_merkle_tree: UncheckedAccount<'info>,
+
+    pub compression_program: Program<'info, SplAccountCompressionProgramAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates or updates the per slot price of a land. Only the current owner of the land cNFT
+/// can set it.
+pub fn handle_set_land_pricing<'info>(
+    ctx: Context<'_, '_, '_, 'info, SetLandPricingAccounts<'info>>,
+    land_asset_id: Pubkey,
+    price: u64,
+    land_asset_id_leaf_data: LeafData,
+) -> Result<()> {
+    if price == 0 {
+        return err!(CustomErrors::ZeroPrice);
+    }
+
+    let asset_id = get_asset_id(
+        &ctx.accounts.land_merkle_tree.key(),
+        land_asset_id_leaf_data.nonce,
+    );
+    require_keys_eq!(land_asset_id, asset_id);
+
+    ctx.accounts
+        .central_authority
+        .check_received_creator_hash(&land_asset_id_leaf_data.creator_hash)?;
+
+    let proof: Vec<_> = ctx
+        .remaining_accounts
+        .iter()
+        .map(|account| (account, false, false))
+        .collect();
+    land_asset_id_leaf_data.verify_leaf(
+        &ctx.accounts.compression_program.to_account_info(),
+        &ctx.accounts.land_merkle_tree.to_account_info(),
+        land_asset_id,
+        ctx.accounts.land_owner.key(),
+        ctx.accounts.land_delegate.key(),
+        &proof,
+    )?;
+
+    let land_pricing = &mut ctx.accounts.land_pricing;
+    land_pricing.land_asset_id = land_asset_id;
+    land_pricing.owner = ctx.accounts.land_owner.key();
+    land_pricing.price = price;
+    land_pricing.bump = ctx.bumps.land_pricing;
+
+    emit_cpi!(LandPricingSet {
+        land_asset_id,
+        owner: land_pricing.owner,
+        price,
+    });
+
+    Ok(())
+}
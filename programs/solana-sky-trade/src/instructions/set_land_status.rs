@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::*, state::*};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(land_asset_id: Pubkey)]
+pub struct SetLandStatusPayload<'info> {
+    #[account(
+        seeds = [b"central_authority"],
+        bump
+        )]
+    pub central_authority: Box<Account<'info, Data>>,
+
+    #[account(mut)]
+    pub pause_guardian: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = pause_guardian,
+        space = LandStatus::MAX_SIZE,
+        seeds = [b"land_status", land_asset_id.as_ref()],
+        bump
+    )]
+    pub land_status: Account<'info, LandStatus>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Suspends or reinstates a single land.
+pub fn handle_set_land_status(
+    ctx: Context<SetLandStatusPayload>,
+    land_asset_id: Pubkey,
+    suspended: bool,
+) -> Result<()> {
+    ctx.accounts
+        .central_authority
+        .check_pause_guardian(ctx.accounts.pause_guardian.key())?;
+
+    let land_status = &mut ctx.accounts.land_status;
+    land_status.land_asset_id = land_asset_id;
+    land_status.suspended = suspended;
+    land_status.bump = ctx.bumps.land_status;
+
+    emit_cpi!(LandStatusUpdated {
+        land_asset_id,
+        suspended,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::*, state::*};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPausePayload<'info> {
+    #[account(
+        mut,
+        seeds = [b"central_authority"],
+        bump
+        )]
+    pub central_authority: Box<Account<'info, Data>>,
+
+    pub pause_guardian: Signer<'info>,
+}
+
+/// Stops or resumes new rentals and settlements across every land.
+pub fn handle_set_pause(
+    ctx: Context<SetPausePayload>,
+    rentals_paused: bool,
+    settlements_paused: bool,
+) -> Result<()> {
+    let data = &mut ctx.accounts.central_authority;
+    data.check_pause_guardian(ctx.accounts.pause_guardian.key())?;
+
+    data.rentals_paused = rentals_paused;
+    data.settlements_paused = settlements_paused;
+
+    emit_cpi!(PauseUpdated {
+        rentals_paused,
+        settlements_paused,
+    });
+
+    Ok(())
+}
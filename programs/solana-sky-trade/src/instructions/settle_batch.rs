@@ -0,0 +1,289 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address,
+    token::{self, transfer, CloseAccount, Mint, Token, TokenAccount, Transfer},
+};
+use mpl_bubblegum::utils::get_asset_id;
+
+use crate::{errors::*, events::*, state::*};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(leaf_data: LeafData)]
+pub struct SettleBatchAccounts<'info> {
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"central_authority"],
+        bump
+        )]
+    pub central_authority: Box<Account<'info, Data>>,
+
+    /// CHECK: checked at ix
+    #[account(mut)]
+    pub fee_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = fee_account,
+    )]
+    pub fee_account_ata: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: checked at ix
+    pub land_owner: UncheckedAccount<'info>,
+
+    /// CHECK: checked at ix
+    pub land_delegate: UncheckedAccount<'info>,
+
+    /// CHECK: checked at ix
+    pub payment_receiver: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = payment_receiver,
+    )]
+    pub payment_receiver_ata: Box<Account<'info, TokenAccount>>,
+
+    /// Whoever settles the escrows
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    /// Receives the keeper reward, which stays with the fee account when omitted
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = keeper,
+    )]
+    pub keeper_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: Don't need to check
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: may not be initialized, the land is active unless suspended by an admin
+    #[account(
+        seeds = [b"land_status", get_asset_id(&merkle_tree.key(), leaf_data.nonce).as_ref()],
+        bump
+    )]
+    pub land_status: UncheckedAccount<'info>,
+
+    pub compression_program: Program<'info, SplAccountCompressionProgramAccount>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+struct BatchEntry<'a, 'info> {
+    escrow: &'a AccountInfo<'info>,
+    escrow_ata: &'a AccountInfo<'info>,
+    terms: SettlementTerms,
+}
+
+/// Settles every expired escrow of a single land in one go.
+///
+/// `remaining_accounts` holds the `proof_len` proof nodes of the land leaf, followed by one
+/// `(escrow, escrow_ata)` pair per escrow. The whole balance is first gathered in the ATA of the
+/// first escrow, so the fee account and the payment receiver are each paid once.
+pub fn handle_settle_batch<'info>(
+    ctx: Context<'_, '_, '_, 'info, SettleBatchAccounts<'info>>,
+    leaf_data: LeafData,
+    proof_len: u8,
+) -> Result<()> {
+    if ctx.accounts.fee_account.key() != ctx.accounts.central_authority.fee_account {
+        return err!(CustomErrors::InvalidReceiver);
+    }
+
+    if ctx.accounts.mint.key() != ctx.accounts.central_authority.mint_address {
+        return err!(CustomErrors::InvalidMint);
+    }
+
+    if ctx.accounts.central_authority.settlements_paused {
+        return err!(CustomErrors::SettlementsPaused);
+    }
+    if LandStatus::is_suspended(&ctx.accounts.land_status)? {
+        return err!(CustomErrors::LandSuspended);
+    }
+
+    let proof_len = proof_len as usize;
+    if ctx.remaining_accounts.len() < proof_len {
+        return err!(CustomErrors::InvalidRemainingAccountsPassed);
+    }
+    let (proof, escrow_accounts) = ctx.remaining_accounts.split_at(proof_len);
+    if escrow_accounts.is_empty() || escrow_accounts.len() % 2 != 0 {
+        return err!(CustomErrors::InvalidRemainingAccountsPassed);
+    }
+
+    let asset_id = get_asset_id(&ctx.accounts.merkle_tree.key(), leaf_data.nonce);
+
+    ctx.accounts
+        .central_authority
+        .check_received_creator_hash(&leaf_data.creator_hash)?;
+
+    // A single leaf verification covers every escrow of the land
+    leaf_data.verify_leaf(
+        &ctx.accounts.compression_program.to_account_info(),
+        &ctx.accounts.merkle_tree.to_account_info(),
+        asset_id,
+        ctx.accounts.land_owner.key(),
+        ctx.accounts.land_delegate.key(),
+        proof
+            .iter()
+            .map(|account| (account, false, false))
+            .collect::<Vec<_>>()
+            .as_slice(),
+    )?;
+
+    ctx.accounts.central_authority.check_payment_receiver(
+        &ctx.accounts.land_owner,
+        ctx.accounts.payment_receiver.key(),
+    )?;
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let mint = ctx.accounts.mint.key();
+
+    let mut entries: Vec<BatchEntry> = Vec::with_capacity(escrow_accounts.len() / 2);
+    let mut total_cost: u64 = 0;
+    let mut total_fee: u64 = 0;
+    for pair in escrow_accounts.chunks(2) {
+        let (escrow, escrow_ata) = (&pair[0], &pair[1]);
+
+        if entries.iter().any(|entry| entry.escrow.key() == escrow.key()) {
+            return err!(CustomErrors::InvalidRemainingAccountsPassed);
+        }
+
+        let terms = SettlementTerms::load(escrow)?;
+        require_keys_eq!(terms.land_asset_id, asset_id);
+        if terms.disputed {
+            return err!(CustomErrors::RentalDisputed);
+        }
+        if terms.pending {
+            return err!(CustomErrors::RentalPending);
+        }
+        if ctx.accounts.central_authority.settlement_time(terms.end_time) > current_timestamp {
+            return err!(CustomErrors::InvalidTransferTime);
+        }
+        require_keys_eq!(
+            escrow_ata.key(),
+            get_associated_token_address(&escrow.key(), &mint),
+            CustomErrors::InvalidRemainingAccountsPassed
+        );
+
+        total_cost = total_cost
+            .checked_add(terms.expected_cost)
+            .ok_or(CustomErrors::MathOverflow)?;
+        total_fee = total_fee
+            .checked_add(terms.fee_quota)
+            .ok_or(CustomErrors::MathOverflow)?;
+
+        entries.push(BatchEntry {
+            escrow,
+            escrow_ata,
+            terms,
+        });
+    }
+
+    msg!("settling {} escrows", entries.len());
+    msg!("total cost {}", total_cost);
+    msg!("total fee {}", total_fee);
+
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let (collector, others) = entries.split_first().unwrap();
+
+    for entry in others {
+        transfer(
+            CpiContext::new(
+                token_program.clone(),
+                Transfer {
+                    from: entry.escrow_ata.clone(),
+                    to: collector.escrow_ata.clone(),
+                    authority: entry.escrow.clone(),
+                },
+            )
+            .with_signer(&[&entry.terms.signer_seeds()]),
+            entry.terms.expected_cost,
+        )?;
+    }
+
+    // The keeper reward is carved out of the fee
+    let mut keeper_reward = 0;
+    if let Some(keeper_ata) = &ctx.accounts.keeper_ata {
+        keeper_reward = ctx.accounts.central_authority.keeper_reward(total_fee)?;
+        transfer(
+            CpiContext::new(
+                token_program.clone(),
+                Transfer {
+                    from: collector.escrow_ata.clone(),
+                    to: keeper_ata.to_account_info(),
+                    authority: collector.escrow.clone(),
+                },
+            )
+            .with_signer(&[&collector.terms.signer_seeds()]),
+            keeper_reward,
+        )?;
+    }
+
+    transfer(
+        CpiContext::new(
+            token_program.clone(),
+            Transfer {
+                from: collector.escrow_ata.clone(),
+                to: ctx.accounts.fee_account_ata.to_account_info(),
+                authority: collector.escrow.clone(),
+            },
+        )
+        .with_signer(&[&collector.terms.signer_seeds()]),
+        total_fee - keeper_reward,
+    )?;
+    transfer(
+        CpiContext::new(
+            token_program.clone(),
+            Transfer {
+                from: collector.escrow_ata.clone(),
+                to: ctx.accounts.payment_receiver_ata.to_account_info(),
+                authority: collector.escrow.clone(),
+            },
+        )
+        .with_signer(&[&collector.terms.signer_seeds()]),
+        total_cost - total_fee,
+    )?;
+
+    let rent_destination = if ctx.accounts.central_authority.rent_to_keeper {
+        ctx.accounts.keeper.to_account_info()
+    } else {
+        ctx.accounts.fee_account.to_account_info()
+    };
+
+    for entry in &entries {
+        token::close_account(
+            CpiContext::new(
+                token_program.clone(),
+                CloseAccount {
+                    account: entry.escrow_ata.clone(),
+                    destination: rent_destination.clone(),
+                    authority: entry.escrow.clone(),
+                },
+            )
+            .with_signer(&[&entry.terms.signer_seeds()]),
+        )?;
+        close_program_account(entry.escrow, &rent_destination)?;
+        ctx.accounts.central_authority.escrow_closed()?;
+
+        emit_cpi!(RentalSettled {
+            land_asset_id: asset_id,
+            rent_escrow: entry.escrow.key(),
+            payment_receiver: ctx.accounts.payment_receiver.key(),
+            expected_cost: entry.terms.expected_cost,
+            fee_quota: entry.terms.fee_quota,
+        });
+    }
+    if ctx.accounts.keeper_ata.is_some() {
+        emit_cpi!(KeeperRewarded {
+            keeper: ctx.accounts.keeper.key(),
+            reward: keeper_reward,
+            escrow_count: entries.len() as u32,
+        });
+    }
+
+    Ok(())
+}
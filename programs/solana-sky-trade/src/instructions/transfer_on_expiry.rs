@@ -1,24 +1,26 @@
-use crate::{Auction, LeafData, RentEscrow, SplAccountCompressionProgramAccount, Data};
-use anchor_lang::{prelude::*, solana_program::system_program};
-use chrono::*;
+use crate::{
+    close_program_account, Data, LandStatus, LeafData, SettlementTerms,
+    SplAccountCompressionProgramAccount,
+};
+use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
     token::{transfer, Mint, Token, TokenAccount, Transfer, CloseAccount, self},
 };
-use mpl_bubblegum::{
-    instructions::{VerifyLeafCpi, VerifyLeafCpiAccounts, VerifyLeafInstructionArgs},
-    types::LeafSchema,
-    utils::get_asset_id,
-};
+use mpl_bubblegum::utils::get_asset_id;
 
 use crate::errors::*;
+use crate::events::*;
 
+#[event_cpi]
 #[derive(Accounts)]
+#[instruction(leaf_data: LeafData)]
 pub struct TransferOnExpiryAccounts<'info> {
     #[account(mut)]
     pub mint: Account<'info, Mint>,
 
     #[account(
+        mut,
         seeds = [b"central_authority"],
         bump
         )]
@@ -51,10 +53,9 @@ pub struct TransferOnExpiryAccounts<'info> {
             )]
     payment_receiver_ata: Account<'info, TokenAccount>,
 
-    #[account(mut, 
-        close = fee_account,
-    )]
-    rent_escrow: Account<'info, RentEscrow>,
+    /// CHECK: current or legacy escrow, deserialized at ix
+    #[account(mut)]
+    rent_escrow: UncheckedAccount<'info>,
 
     #[account(
        mut,
@@ -62,9 +63,29 @@ pub struct TransferOnExpiryAccounts<'info> {
         associated_token::authority = rent_escrow,
     )]
     rent_escrow_ata: Account<'info, TokenAccount>,
+
+    /// Whoever settles the escrow
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    /// Receives the keeper reward, which stays with the fee account when omitted
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = keeper,
+    )]
+    pub keeper_ata: Option<Account<'info, TokenAccount>>,
+
     /// CHECK: Don't need to check
     merkle_tree: AccountInfo<'info>,
 
+    /// CHECK: may not be initialized, the land is active unless suspended by an admin
+    #[account(
+        seeds = [b"land_status", get_asset_id(&merkle_tree.key(), leaf_data.nonce).as_ref()],
+        bump
+    )]
+    land_status: UncheckedAccount<'info>,
+
     pub compression_program: Program<'info, SplAccountCompressionProgramAccount>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Program<'info, Token>,
@@ -94,16 +115,39 @@ impl<'info> TransferOnExpiryAccounts<'info> {
         )
     }
 
+    fn transfer_keeper_ctx(
+        &self,
+        keeper_ata: &Account<'info, TokenAccount>,
+    ) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.rent_escrow_ata.to_account_info(),
+                to: keeper_ata.to_account_info(),
+                authority: self.rent_escrow.to_account_info(),
+            },
+        )
+    }
+
     fn close_ata_context(&self) -> CpiContext<'_, '_, '_, 'info, CloseAccount<'info>> {
         CpiContext::new(
             self.token_program.to_account_info(),
             CloseAccount {
                 account: self.rent_escrow_ata.to_account_info(),
-                destination: self.fee_account.to_account_info(),
+                destination: self.rent_destination(),
                 authority: self.rent_escrow.to_account_info(),
             },
         )
     }
+
+    /// Receiver of the rent of the closed escrow accounts
+    fn rent_destination(&self) -> AccountInfo<'info> {
+        if self.central_authority.rent_to_keeper {
+            self.keeper.to_account_info()
+        } else {
+            self.fee_account.to_account_info()
+        }
+    }
 }
 
 pub fn handle_transfer_on_expiry<'info>(
@@ -120,15 +164,27 @@ pub fn handle_transfer_on_expiry<'info>(
     if mint_pubkey != ctx.accounts.central_authority.mint_address {
         return err!(CustomErrors::InvalidMint);
     }
-    let expiration_time=DateTime::parse_from_rfc3339(&ctx.accounts.rent_escrow.end_time).unwrap();
-    let expiration_timestamp=expiration_time.timestamp(); 
-    let current_timestamp=Clock::get().unwrap().unix_timestamp; 
-     if expiration_timestamp > current_timestamp {
+    if ctx.accounts.central_authority.settlements_paused {
+        return err!(CustomErrors::SettlementsPaused);
+    }
+    if LandStatus::is_suspended(&ctx.accounts.land_status)? {
+        return err!(CustomErrors::LandSuspended);
+    }
+
+    let escrow = SettlementTerms::load(&ctx.accounts.rent_escrow)?;
+
+    if escrow.disputed {
+        return err!(CustomErrors::RentalDisputed);
+    }
+    if escrow.pending {
+        return err!(CustomErrors::RentalPending);
+    }
+
+    let current_timestamp=Clock::get()?.unix_timestamp; 
+     if ctx.accounts.central_authority.settlement_time(escrow.end_time) > current_timestamp {
         return err!(CustomErrors::InvalidTransferTime);
     } 
 
-    let escrow = &ctx.accounts.rent_escrow;
-
     let expected_cost = escrow.expected_cost;
     let fee_quota = escrow.fee_quota;
 
@@ -137,32 +193,17 @@ pub fn handle_transfer_on_expiry<'info>(
 
     
     let asset_id = get_asset_id(&ctx.accounts.merkle_tree.key(), leaf_data.nonce);
-    let leaf = LeafSchema::V1 {
-        id: asset_id,
-        owner: ctx.accounts.land_owner.key(),
-        delegate: ctx.accounts.land_delegate.key(),
-        nonce: leaf_data.nonce,
-        data_hash: leaf_data.hash,
-        creator_hash: leaf_data.creator_hash,
-    };
-    
-    require_keys_eq!(ctx.accounts.rent_escrow.land_asset_id, asset_id);
-    
+    require_keys_eq!(escrow.land_asset_id, asset_id);
+
     ctx.accounts.central_authority.check_received_creator_hash(&leaf_data.creator_hash)?;
 
     //This checks land_owner as owner
-    VerifyLeafCpi::new(
+    leaf_data.verify_leaf(
         &ctx.accounts.compression_program.to_account_info(),
-        VerifyLeafCpiAccounts {
-            merkle_tree: &ctx.accounts.merkle_tree.to_account_info(),
-        },
-        VerifyLeafInstructionArgs {
-            index: leaf_data.index,
-            root: leaf_data.root,
-            leaf: leaf.hash(),
-        },
-    )
-    .invoke_with_remaining_accounts(
+        &ctx.accounts.merkle_tree.to_account_info(),
+        asset_id,
+        ctx.accounts.land_owner.key(),
+        ctx.accounts.land_delegate.key(),
         ctx.remaining_accounts
             .iter()
             .map(|account| (account, false, false))
@@ -170,37 +211,34 @@ pub fn handle_transfer_on_expiry<'info>(
             .as_slice(),
     )?;
 
-    if ctx.accounts.land_owner.owner.key() == system_program::id().key() {
-        msg!("Land not in auction");
-        if ctx.accounts.land_owner.key() != ctx.accounts.payment_receiver.key() {
-            return err!(CustomErrors::InvalidReceiver);
-        }
-    } else if ctx.accounts.land_owner.owner.key()
-        == ctx.accounts.central_authority.auction_house_address.key()
-    {
-        let mut auction_data: &[u8] = &ctx.accounts.land_owner.data.borrow();
-
-        let auction = Auction::try_deserialize(&mut auction_data)?;
-        msg!("Land in auction. Auction creator is {}", auction.seller);
+    ctx.accounts.central_authority.check_payment_receiver(
+        &ctx.accounts.land_owner,
+        ctx.accounts.payment_receiver.key(),
+    )?;
 
-        if auction.seller.key() != ctx.accounts.payment_receiver.key() {
-            return err!(CustomErrors::InvalidReceiver);
-        }
-    } else {
-        return err!(CustomErrors::InvalidReceiver);
+    // The keeper reward is carved out of the fee
+    let mut keeper_reward = 0;
+    if let Some(keeper_ata) = &ctx.accounts.keeper_ata {
+        keeper_reward = ctx.accounts.central_authority.keeper_reward(fee_quota)?;
+        transfer(
+            ctx.accounts
+                .transfer_keeper_ctx(keeper_ata)
+                .with_signer(&[&escrow.signer_seeds()]),
+            keeper_reward,
+        )?;
     }
 
     transfer(
         ctx.accounts
             .transfer_fee_ctx()
-            .with_signer(&[&escrow.escrow_seeds()]),
-        fee_quota,
+            .with_signer(&[&escrow.signer_seeds()]),
+        fee_quota - keeper_reward,
     )?;
     let final_payment = expected_cost - fee_quota;
     transfer(
         ctx.accounts
             .transfer_receiver_ctx()
-            .with_signer(&[&escrow.escrow_seeds()]),
+            .with_signer(&[&escrow.signer_seeds()]),
         final_payment,
     )?;
 
@@ -208,8 +246,29 @@ pub fn handle_transfer_on_expiry<'info>(
     token::close_account(
         ctx.accounts
             .close_ata_context()
-            .with_signer(&[&escrow.escrow_seeds()]),
+            .with_signer(&[&escrow.signer_seeds()]),
+    )?;
+
+    close_program_account(
+        &ctx.accounts.rent_escrow.to_account_info(),
+        &ctx.accounts.rent_destination(),
     )?;
+    ctx.accounts.central_authority.escrow_closed()?;
+
+    emit_cpi!(RentalSettled {
+        land_asset_id: escrow.land_asset_id,
+        rent_escrow: ctx.accounts.rent_escrow.key(),
+        payment_receiver: ctx.accounts.payment_receiver.key(),
+        expected_cost,
+        fee_quota,
+    });
+    if ctx.accounts.keeper_ata.is_some() {
+        emit_cpi!(KeeperRewarded {
+            keeper: ctx.accounts.keeper.key(),
+            reward: keeper_reward,
+            escrow_count: 1,
+        });
+    }
 
     Ok(())
 }
@@ -0,0 +1,257 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+use mpl_bubblegum::{
+    instructions::{DelegateCpiBuilder, TransferCpiBuilder},
+    utils::get_asset_id,
+};
+
+use crate::{errors::*, events::*, state::*};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct TransferRentalAccounts<'info> {
+    #[account(
+        seeds = [b"central_authority"],
+        bump
+        )]
+    pub central_authority: Box<Account<'info, Data>>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    pub renter: Signer<'info>,
+
+    #[account(mut,
+        associated_token::mint = mint,
+        associated_token::authority = renter
+    )]
+    pub renter_ata: Box<Account<'info, TokenAccount>>,
+
+    pub new_renter: Signer<'info>,
+
+    #[account(mut,
+        associated_token::mint = mint,
+        associated_token::authority = new_renter
+    )]
+    pub new_renter_ata: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: checked at ix
+    pub land_owner: UncheckedAccount<'info>,
+
+    /// CHECK: checked at ix
+    pub land_delegate: UncheckedAccount<'info>,
+
+    /// CHECK: checked at ix
+    pub payment_receiver: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = payment_receiver,
+    )]
+    pub payment_receiver_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut,
+        has_one = renter,
+        has_one = renter_ata,
+        has_one = rental_merkle_tree,
+    )]
+    pub rent_escrow: Box<Account<'info, RentalEscrow>>,
+
+    /// CHECK: This account is checked in the instruction
+    pub land_merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: delegate of the rental cNFT, checked by bubblegum
+    pub rental_delegate: UncheckedAccount<'info>,
+
+    /// CHECK: checked at ix, the rental cNFT is delegated back to it
+    pub minting_signer: UncheckedAccount<'info>,
+
+    /// CHECK: checked by bubblegum
+    #[account(mut)]
+    pub rental_merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: checked by bubblegum
+    pub tree_config: UncheckedAccount<'info>,
+
+    pub bubblegum_program: Program<'info, MplBubblegumProgramAccount>,
+    pub log_wrapper: Program<'info, NoopProgramAccount>,
+    pub compression_program: Program<'info, SplAccountCompressionProgramAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> TransferRentalAccounts<'info> {
+    fn transfer_ctx(
+        &self,
+        to: &Account<'info, TokenAccount>,
+    ) -> CpiContext<'_, '_, '_, 'info, TransferChecked<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            TransferChecked {
+                from: self.new_renter_ata.to_account_info(),
+                mint: self.mint.to_account_info(),
+                to: to.to_account_info(),
+                authority: self.new_renter.to_account_info(),
+            },
+        )
+    }
+}
+
+/// Hands a booked rental over to `new_renter`, optionally sold for `price`.
+///
+/// The new renter pays `price` to the current renter, minus `Data::resale_royalty_bps` going to
+/// the land payment receiver. The rental cNFT is transferred and the escrow now refunds and
+/// settles with the new renter.
+///
+/// Bubblegum resets the leaf delegate to the new owner on transfer, so the new renter also
+/// delegates the rental cNFT back to the minting signer, which `void_rental` burns it through.
+///
+/// `remaining_accounts` holds the `land_proof_len` proof nodes of the land leaf, followed by the
+/// proof of the rental leaf.
+pub fn handle_transfer_rental<'info>(
+    ctx: Context<'_, '_, '_, 'info, TransferRentalAccounts<'info>>,
+    price: u64,
+    land_asset_id_leaf_data: LeafData,
+    rental_leaf_data: LeafData,
+    land_proof_len: u8,
+) -> Result<()> {
+    let central_authority = &ctx.accounts.central_authority;
+    if central_authority.rentals_paused {
+        return err!(CustomErrors::RentalsPaused);
+    }
+
+    if ctx.accounts.mint.key() != central_authority.mint_address {
+        return err!(CustomErrors::InvalidMint);
+    }
+
+    let escrow = &ctx.accounts.rent_escrow;
+    if escrow.is_pending() {
+        return err!(CustomErrors::RentalPending);
+    }
+    if escrow.disputed {
+        return err!(CustomErrors::RentalDisputed);
+    }
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    if current_timestamp >= escrow.end_time {
+        return err!(CustomErrors::RentalEnded);
+    }
+
+    let land_proof_len = land_proof_len as usize;
+    if ctx.remaining_accounts.len() < land_proof_len {
+        return err!(CustomErrors::InvalidRemainingAccountsPassed);
+    }
+    let (land_proof, rental_proof) = ctx.remaining_accounts.split_at(land_proof_len);
+
+    let asset_id = get_asset_id(
+        &ctx.accounts.land_merkle_tree.key(),
+        land_asset_id_leaf_data.nonce,
+    );
+    require_keys_eq!(escrow.land_asset_id, asset_id);
+
+    central_authority.check_received_creator_hash(&land_asset_id_leaf_data.creator_hash)?;
+    let land_proof: Vec<_> = land_proof
+        .iter()
+        .map(|account| (account, false, false))
+        .collect();
+    land_asset_id_leaf_data.verify_leaf(
+        &ctx.accounts.compression_program.to_account_info(),
+        &ctx.accounts.land_merkle_tree.to_account_info(),
+        escrow.land_asset_id,
+        ctx.accounts.land_owner.key(),
+        ctx.accounts.land_delegate.key(),
+        &land_proof,
+    )?;
+
+    central_authority.check_payment_receiver(
+        &ctx.accounts.land_owner,
+        ctx.accounts.payment_receiver.key(),
+    )?;
+
+    central_authority.check_minting_signer(ctx.accounts.minting_signer.key())?;
+
+    let asset_id = get_asset_id(
+        &ctx.accounts.rental_merkle_tree.key(),
+        rental_leaf_data.nonce,
+    );
+    if asset_id != escrow.rental_asset_id {
+        return err!(CustomErrors::InvalidRentalAddressPassed);
+    }
+
+    let royalty = bps_share(price, central_authority.resale_royalty_bps)?;
+    let seller_proceeds = price - royalty;
+
+    msg!("royalty {}", royalty);
+
+    let decimals = ctx.accounts.mint.decimals;
+    for (to, amount) in [
+        (&ctx.accounts.payment_receiver_ata, royalty),
+        (&ctx.accounts.renter_ata, seller_proceeds),
+    ] {
+        if amount > 0 {
+            transfer_checked(ctx.accounts.transfer_ctx(to), amount, decimals)?;
+        }
+    }
+
+    // Bubblegum checks that the renter still owns the rental cNFT
+    let rental_proof: Vec<_> = rental_proof
+        .iter()
+        .map(|account| (account, false, false))
+        .collect();
+    TransferCpiBuilder::new(&ctx.accounts.bubblegum_program.to_account_info())
+        .tree_config(&ctx.accounts.tree_config.to_account_info())
+        .leaf_owner(&ctx.accounts.renter.to_account_info(), true)
+        .leaf_delegate(&ctx.accounts.rental_delegate.to_account_info(), false)
+        .new_leaf_owner(&ctx.accounts.new_renter.to_account_info())
+        .merkle_tree(&ctx.accounts.rental_merkle_tree.to_account_info())
+        .log_wrapper(&ctx.accounts.log_wrapper.to_account_info())
+        .compression_program(&ctx.accounts.compression_program.to_account_info())
+        .system_program(&ctx.accounts.system_program.to_account_info())
+        .root(rental_leaf_data.root)
+        .data_hash(rental_leaf_data.hash)
+        .creator_hash(rental_leaf_data.creator_hash)
+        .nonce(rental_leaf_data.nonce)
+        .index(rental_leaf_data.index)
+        .add_remaining_accounts(&rental_proof)
+        .invoke()?;
+
+    // The root before the transfer is still in the tree changelog, which fast-forwards the proof
+    // over the transfer
+    DelegateCpiBuilder::new(&ctx.accounts.bubblegum_program.to_account_info())
+        .tree_config(&ctx.accounts.tree_config.to_account_info())
+        .leaf_owner(&ctx.accounts.new_renter.to_account_info())
+        .previous_leaf_delegate(&ctx.accounts.new_renter.to_account_info())
+        .new_leaf_delegate(&ctx.accounts.minting_signer.to_account_info())
+        .merkle_tree(&ctx.accounts.rental_merkle_tree.to_account_info())
+        .log_wrapper(&ctx.accounts.log_wrapper.to_account_info())
+        .compression_program(&ctx.accounts.compression_program.to_account_info())
+        .system_program(&ctx.accounts.system_program.to_account_info())
+        .root(rental_leaf_data.root)
+        .data_hash(rental_leaf_data.hash)
+        .creator_hash(rental_leaf_data.creator_hash)
+        .nonce(rental_leaf_data.nonce)
+        .index(rental_leaf_data.index)
+        .add_remaining_accounts(&rental_proof)
+        .invoke()?;
+
+    let escrow = &mut ctx.accounts.rent_escrow;
+    let previous_renter = escrow.renter;
+    escrow.renter = ctx.accounts.new_renter.key();
+    escrow.renter_ata = ctx.accounts.new_renter_ata.key();
+
+    emit_cpi!(RentalTransferred {
+        land_asset_id: escrow.land_asset_id,
+        rental_asset_id: escrow.rental_asset_id,
+        rent_escrow: escrow.key(),
+        previous_renter,
+        renter: escrow.renter,
+        price,
+        royalty,
+    });
+
+    Ok(())
+}
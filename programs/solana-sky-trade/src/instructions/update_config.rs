@@ -1,7 +1,8 @@
-use crate::{errors::*, state::*};
+use crate::{errors::*, events::*, state::*};
 use anchor_lang::prelude::*;
 use anchor_spl::token::Mint;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct UpdateConfigPayload<'info> {
     #[account(
@@ -19,84 +20,131 @@ pub struct UpdateConfigPayload<'info> {
     pub mint_account: Account<'info, Mint>,
 }
 
+/// Settings owned by the config admin, the `centralized_account`. Pricing and fees have their own
+/// admins, see `UpdatePricingData` and `UpdateFeesData`.
 #[derive(Debug, Clone, AnchorDeserialize, AnchorSerialize)]
 pub struct UpdateConfigData {
-    pub base_cost: Option<f64>,
-    pub admin_quota: Option<f64>,
     pub auction_house_address: Option<Pubkey>,
-    pub multiplier: Option<f64>,
-    pub fee_account: Option<Pubkey>,
+    pub dispute_window_seconds: Option<i64>,
+    pub arbiter: Option<Pubkey>,
+    pub authority_timelock_seconds: Option<i64>,
     pub mint_address: Option<Pubkey>,
-    pub centralized_account: Option<Pubkey>,
+    /// Price of a slot in the new mint, required with and only accepted along a mint change
+    pub base_cost: Option<u64>,
     pub royalties_receiver: Option<Pubkey>,
     pub mint_creator: Option<Pubkey>,
     pub verification_creator: Option<Pubkey>,
+    pub pricing_admin: Option<Pubkey>,
+    pub fee_admin: Option<Pubkey>,
+    pub minting_signer: Option<Pubkey>,
+    pub pause_guardian: Option<Pubkey>,
+    pub slot_minutes: Option<u16>,
+    pub max_advance_seconds: Option<i64>,
+    pub min_lead_seconds: Option<i64>,
+    pub running_slot_bookable: Option<bool>,
 }
 
-pub fn handle_update_config(
-    ctx: Context<UpdateConfigPayload>,
-    payload: UpdateConfigData,
-) -> Result<()> {
-    if ctx.accounts.central_authority.centralized_account != ctx.accounts.centralized_account.key()
-    {
-        return err!(CustomErrors::InvalidAuthority);
-    }
+impl UpdateConfigData {
+    /// Upper bound of the serialized size, used to allocate governance proposals
+    pub const MAX_SIZE: usize =
+        33 + 9 + 33 + 9 + 33 + 9 + 33 + 33 + 33 + 33 + 33 + 33 + 33 + 3 + 9 + 9 + 2;
 
-    match payload.base_cost {
-        Some(value) => {
-            ctx.accounts.central_authority.base_cost =
-                (value * f64::powf(10.0, ctx.accounts.mint_account.decimals.into())) as u64;
+    /// Writes the provided settings to `data`, then validates the resulting config
+    pub fn apply(self, data: &mut Data) -> Result<()> {
+        if let Some(value) = self.auction_house_address {
+            data.auction_house_address = value;
         }
-        None => {}
-    }
 
-    match payload.admin_quota {
-        Some(value) => {
-            ctx.accounts.central_authority.admin_quota = value;
+        // Prices are in raw units of the mint, so they are reset along with it
+        match (self.mint_address, self.base_cost) {
+            (Some(mint_address), base_cost) if mint_address != data.mint_address => {
+                if data.open_escrows > 0 {
+                    return err!(CustomErrors::EscrowsStillOpen);
+                }
+                data.mint_address = mint_address;
+                data.base_cost = base_cost.ok_or(CustomErrors::MintChangeWithoutBaseCost)?;
+            }
+            (_, Some(_)) => return err!(CustomErrors::BaseCostWithoutMintChange),
+            _ => {}
         }
-        None => {}
-    }
 
-    match payload.auction_house_address {
-        Some(value) => {
-            ctx.accounts.central_authority.auction_house_address = value;
+        if let Some(royalties_receiver) = self.royalties_receiver {
+            data.land_creators.royalties_receiver = royalties_receiver;
         }
-        None => {}
-    }
-    match payload.mint_address {
-        Some(value) => {
-            ctx.accounts.central_authority.mint_address = value;
+        if let Some(mint_creator) = self.mint_creator {
+            data.land_creators.mint_creator = mint_creator;
+        }
+        if let Some(verification_creator) = self.verification_creator {
+            data.land_creators.verification_creator = verification_creator;
         }
-        None => {}
-    }
 
-    match payload.centralized_account {
-        Some(value) => {
-            ctx.accounts.central_authority.centralized_account = value;
+        if let Some(dispute_window_seconds) = self.dispute_window_seconds {
+            data.dispute_window_seconds = dispute_window_seconds;
+        }
+        if let Some(arbiter) = self.arbiter {
+            data.arbiter = arbiter;
         }
-        None => {}
-    }
 
-    match payload.fee_account {
-        Some(value) => ctx.accounts.central_authority.fee_account = value,
-        None => {}
-    }
+        if let Some(authority_timelock_seconds) = self.authority_timelock_seconds {
+            data.authority_timelock_seconds = authority_timelock_seconds;
+        }
+
+        if let Some(pricing_admin) = self.pricing_admin {
+            data.roles.pricing_admin = pricing_admin;
+        }
+        if let Some(fee_admin) = self.fee_admin {
+            data.roles.fee_admin = fee_admin;
+        }
+        if let Some(minting_signer) = self.minting_signer {
+            data.roles.minting_signer = minting_signer;
+        }
+        if let Some(pause_guardian) = self.pause_guardian {
+            data.roles.pause_guardian = pause_guardian;
+        }
+
+        if let Some(slot_minutes) = self.slot_minutes {
+            data.slot_minutes = slot_minutes;
+        }
+        if let Some(max_advance_seconds) = self.max_advance_seconds {
+            data.max_advance_seconds = max_advance_seconds;
+        }
+        if let Some(min_lead_seconds) = self.min_lead_seconds {
+            data.min_lead_seconds = min_lead_seconds;
+        }
+        if let Some(running_slot_bookable) = self.running_slot_bookable {
+            data.running_slot_bookable = running_slot_bookable;
+        }
 
-    if let Some(royalties_receiver) = payload.royalties_receiver {
-        ctx.accounts
-            .central_authority
-            .land_creators
-            .royalties_receiver = royalties_receiver;
+        data.validate()
     }
-    if let Some(mint_creator) = payload.mint_creator {
-        ctx.accounts.central_authority.land_creators.mint_creator = mint_creator;
+}
+
+pub fn handle_update_config(
+    ctx: Context<UpdateConfigPayload>,
+    payload: UpdateConfigData,
+) -> Result<()> {
+    if ctx.accounts.central_authority.centralized_account != ctx.accounts.centralized_account.key()
+    {
+        return err!(CustomErrors::InvalidAuthority);
     }
-    if let Some(verification_creator) = payload.verification_creator {
-        ctx.accounts
-            .central_authority
-            .land_creators
-            .verification_creator = verification_creator;
+
+    // Once governance is set up, config changes go through `propose_config`
+    if ctx.accounts.central_authority.config_governed {
+        return err!(CustomErrors::ConfigUnderGovernance);
     }
 
+    let old = (*ctx.accounts.central_authority).clone();
+    payload.apply(&mut ctx.accounts.central_authority)?;
+
+    // A new mint has to be passed along, so it is known to be a valid mint
+    ctx.accounts
+        .central_authority
+        .check_mint_account(&ctx.accounts.mint_account)?;
+
+    emit_cpi!(ConfigUpdated {
+        old,
+        new: (*ctx.accounts.central_authority).clone(),
+    });
+
     Ok(())
 }
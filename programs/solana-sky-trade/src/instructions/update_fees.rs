@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::*, events::*, state::*};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateFeesPayload<'info> {
+    #[account(
+        mut,
+        seeds = [b"central_authority"],
+        bump
+        )]
+    pub central_authority: Box<Account<'info, Data>>,
+
+    pub fee_admin: Signer<'info>,
+}
+
+/// Settings owned by the fee admin
+#[derive(Debug, Clone, AnchorDeserialize, AnchorSerialize)]
+pub struct UpdateFeesData {
+    pub admin_quota_bps: Option<u16>,
+    pub fee_account: Option<Pubkey>,
+    pub keeper_reward_bps: Option<u16>,
+    pub rent_to_keeper: Option<bool>,
+    pub resale_royalty_bps: Option<u16>,
+}
+
+impl UpdateFeesData {
+    /// Upper bound of the serialized size, used to allocate governance proposals
+    pub const MAX_SIZE: usize = 3 + 33 + 3 + 2 + 3;
+
+    /// Writes the provided settings to `data`, then validates the resulting config
+    pub fn apply(self, data: &mut Data) -> Result<()> {
+        if let Some(value) = self.admin_quota_bps {
+            data.admin_quota_bps = value;
+        }
+
+        if let Some(value) = self.fee_account {
+            data.fee_account = value;
+        }
+
+        if let Some(keeper_reward_bps) = self.keeper_reward_bps {
+            data.keeper_reward_bps = keeper_reward_bps;
+        }
+        if let Some(rent_to_keeper) = self.rent_to_keeper {
+            data.rent_to_keeper = rent_to_keeper;
+        }
+
+        if let Some(resale_royalty_bps) = self.resale_royalty_bps {
+            data.resale_royalty_bps = resale_royalty_bps;
+        }
+
+        data.validate()
+    }
+}
+
+pub fn handle_update_fees(ctx: Context<UpdateFeesPayload>, payload: UpdateFeesData) -> Result<()> {
+    ctx.accounts
+        .central_authority
+        .check_fee_admin(ctx.accounts.fee_admin.key())?;
+
+    // Once governance is set up, fee changes go through `propose_config`
+    if ctx.accounts.central_authority.config_governed {
+        return err!(CustomErrors::ConfigUnderGovernance);
+    }
+
+    let old = (**ctx.accounts.central_authority).clone();
+    payload.apply(&mut ctx.accounts.central_authority)?;
+
+    emit_cpi!(ConfigUpdated {
+        old,
+        new: (**ctx.accounts.central_authority).clone(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::*, events::*, state::*};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdatePricingPayload<'info> {
+    #[account(
+        mut,
+        seeds = [b"central_authority"],
+        bump
+        )]
+    pub central_authority: Box<Account<'info, Data>>,
+
+    pub pricing_admin: Signer<'info>,
+}
+
+/// Settings owned by the pricing admin
+#[derive(Debug, Clone, AnchorDeserialize, AnchorSerialize)]
+pub struct UpdatePricingData {
+    /// Raw units of the configured mint
+    pub base_cost: Option<u64>,
+    pub hour_multipliers_bps: Option<[u16; 24]>,
+    pub weekday_multipliers_bps: Option<[u16; 7]>,
+    pub cancellation_tiers: Option<Vec<CancellationTier>>,
+}
+
+impl UpdatePricingData {
+    /// Upper bound of the serialized size, used to allocate governance proposals
+    pub const MAX_SIZE: usize = 9
+        + (1 + 2 * 24)
+        + (1 + 2 * 7)
+        + (1 + 4 + MAX_CANCELLATION_TIERS * CancellationTier::INIT_SPACE);
+
+    /// Writes the provided settings to `data`, then validates the resulting config
+    pub fn apply(self, data: &mut Data) -> Result<()> {
+        if let Some(value) = self.base_cost {
+            data.base_cost = value;
+        }
+
+        if let Some(hour_multipliers_bps) = self.hour_multipliers_bps {
+            data.hour_multipliers_bps = hour_multipliers_bps;
+        }
+        if let Some(weekday_multipliers_bps) = self.weekday_multipliers_bps {
+            data.weekday_multipliers_bps = weekday_multipliers_bps;
+        }
+
+        if let Some(cancellation_tiers) = self.cancellation_tiers {
+            data.cancellation_tiers = cancellation_tiers;
+        }
+
+        data.validate()
+    }
+}
+
+pub fn handle_update_pricing(
+    ctx: Context<UpdatePricingPayload>,
+    payload: UpdatePricingData,
+) -> Result<()> {
+    ctx.accounts
+        .central_authority
+        .check_pricing_admin(ctx.accounts.pricing_admin.key())?;
+
+    // Once governance is set up, pricing changes go through `propose_config`
+    if ctx.accounts.central_authority.config_governed {
+        return err!(CustomErrors::ConfigUnderGovernance);
+    }
+
+    let old = (**ctx.accounts.central_authority).clone();
+    payload.apply(&mut ctx.accounts.central_authority)?;
+
+    emit_cpi!(ConfigUpdated {
+        old,
+        new: (**ctx.accounts.central_authority).clone(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,223 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, transfer, CloseAccount, Mint, Token, TokenAccount, Transfer},
+};
+use mpl_bubblegum::{instructions::BurnCpiBuilder, utils::get_asset_id};
+
+use crate::{errors::*, events::*, state::*};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct VoidRentalAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"central_authority"],
+        bump
+        )]
+    pub central_authority: Box<Account<'info, Data>>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    pub pause_guardian: Signer<'info>,
+
+    /// CHECK: checked against the escrow
+    pub renter: UncheckedAccount<'info>,
+
+    #[account(mut,
+        associated_token::mint = mint,
+        associated_token::authority = renter
+    )]
+    pub renter_ata: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: checked at ix
+    #[account(mut)]
+    pub fee_account: UncheckedAccount<'info>,
+
+    #[account(mut,
+        has_one = renter,
+        has_one = renter_ata,
+        has_one = rental_merkle_tree,
+        close = fee_account,
+    )]
+    pub rent_escrow: Box<Account<'info, RentalEscrow>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = rent_escrow,
+    )]
+    pub rent_escrow_ata: Box<Account<'info, TokenAccount>>,
+
+    /// Only required while the rental has not ended, the bookings of past days may be closed
+    #[account(
+        mut,
+        seeds = [
+            b"bookings",
+            rent_escrow.land_asset_id.as_ref(),
+            &DayBookings::seed_day(rent_escrow.start_time, 0),
+        ],
+        bump
+    )]
+    pub day_bookings: Option<Box<Account<'info, DayBookings>>>,
+
+    /// Only required when the rental continues past midnight and has not ended
+    #[account(
+        mut,
+        seeds = [
+            b"bookings",
+            rent_escrow.land_asset_id.as_ref(),
+            &DayBookings::seed_day(rent_escrow.start_time, 1),
+        ],
+        bump
+    )]
+    pub next_day_bookings: Option<Box<Account<'info, DayBookings>>>,
+
+    /// Delegate of the rental cNFT, the minting signer unless the renter changed it
+    pub rental_delegate: Signer<'info>,
+
+    /// CHECK: checked by bubblegum
+    #[account(mut)]
+    pub rental_merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: checked by bubblegum
+    pub tree_config: UncheckedAccount<'info>,
+
+    pub bubblegum_program: Program<'info, MplBubblegumProgramAccount>,
+    pub log_wrapper: Program<'info, NoopProgramAccount>,
+    pub compression_program: Program<'info, SplAccountCompressionProgramAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> VoidRentalAccounts<'info> {
+    fn transfer_refund_ctx(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.rent_escrow_ata.to_account_info(),
+                to: self.renter_ata.to_account_info(),
+                authority: self.rent_escrow.to_account_info(),
+            },
+        )
+    }
+
+    fn close_ata_context(&self) -> CpiContext<'_, '_, '_, 'info, CloseAccount<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.rent_escrow_ata.to_account_info(),
+                destination: self.fee_account.to_account_info(),
+                authority: self.rent_escrow.to_account_info(),
+            },
+        )
+    }
+}
+
+/// Voids a rental that can't take place, typically because the airspace was closed.
+///
+/// The renter gets the whole rental cost back, fee included, the rental cNFT is burned and the
+/// booked slots are freed. `reason` is an off-chain defined code, only recorded in the event.
+pub fn handle_void_rental<'info>(
+    ctx: Context<'_, '_, '_, 'info, VoidRentalAccounts<'info>>,
+    rental_leaf_data: LeafData,
+    reason: u8,
+) -> Result<()> {
+    ctx.accounts
+        .central_authority
+        .check_pause_guardian(ctx.accounts.pause_guardian.key())?;
+
+    if ctx.accounts.fee_account.key() != ctx.accounts.central_authority.fee_account {
+        return err!(CustomErrors::InvalidReceiver);
+    }
+
+    if ctx.accounts.mint.key() != ctx.accounts.central_authority.mint_address {
+        return err!(CustomErrors::InvalidMint);
+    }
+
+    if ctx.accounts.rent_escrow.is_pending() {
+        return err!(CustomErrors::RentalPending);
+    }
+
+    // The slots of an ended rental are in the past, there is nothing left to free
+    if Clock::get()?.unix_timestamp < ctx.accounts.rent_escrow.end_time {
+        let day_bookings = ctx
+            .accounts
+            .day_bookings
+            .as_deref_mut()
+            .ok_or(CustomErrors::MissingDayBookings)?;
+        release_slots(
+            day_bookings,
+            ctx.accounts
+                .next_day_bookings
+                .as_deref_mut()
+                .map(|bookings| &mut **bookings),
+            ctx.accounts.rent_escrow.land_asset_id,
+            ctx.accounts.rent_escrow.start_time,
+            ctx.accounts.rent_escrow.end_time,
+        )?;
+    }
+
+    let escrow = &ctx.accounts.rent_escrow;
+
+    let asset_id = get_asset_id(
+        &ctx.accounts.rental_merkle_tree.key(),
+        rental_leaf_data.nonce,
+    );
+    if asset_id != escrow.rental_asset_id {
+        return err!(CustomErrors::InvalidRentalAddressPassed);
+    }
+
+    // Bubblegum checks that the renter still owns the rental cNFT
+    let proof: Vec<_> = ctx
+        .remaining_accounts
+        .iter()
+        .map(|account| (account, false, false))
+        .collect();
+    BurnCpiBuilder::new(&ctx.accounts.bubblegum_program.to_account_info())
+        .tree_config(&ctx.accounts.tree_config.to_account_info())
+        .leaf_owner(&ctx.accounts.renter.to_account_info(), false)
+        .leaf_delegate(&ctx.accounts.rental_delegate.to_account_info(), true)
+        .merkle_tree(&ctx.accounts.rental_merkle_tree.to_account_info())
+        .log_wrapper(&ctx.accounts.log_wrapper.to_account_info())
+        .compression_program(&ctx.accounts.compression_program.to_account_info())
+        .system_program(&ctx.accounts.system_program.to_account_info())
+        .root(rental_leaf_data.root)
+        .data_hash(rental_leaf_data.hash)
+        .creator_hash(rental_leaf_data.creator_hash)
+        .nonce(rental_leaf_data.nonce)
+        .index(rental_leaf_data.index)
+        .add_remaining_accounts(&proof)
+        .invoke()?;
+
+    let refund = escrow.expected_cost;
+    msg!("refund {}", refund);
+
+    if refund > 0 {
+        transfer(
+            ctx.accounts
+                .transfer_refund_ctx()
+                .with_signer(&[&escrow.escrow_seeds()]),
+            refund,
+        )?;
+    }
+
+    token::close_account(
+        ctx.accounts
+            .close_ata_context()
+            .with_signer(&[&escrow.escrow_seeds()]),
+    )?;
+    ctx.accounts.central_authority.escrow_closed()?;
+
+    emit_cpi!(RentalVoided {
+        land_asset_id: escrow.land_asset_id,
+        rental_asset_id: escrow.rental_asset_id,
+        rent_escrow: escrow.key(),
+        renter: escrow.renter,
+        refund,
+        reason,
+    });
+
+    Ok(())
+}
@@ -1,10 +1,12 @@
 use anchor_lang::prelude::*;
 
 pub mod errors;
+pub mod events;
 pub mod instructions;
 pub mod state;
 
 pub use errors::*;
+pub use events::*;
 pub use instructions::*;
 pub use state::*;
 
@@ -22,18 +24,43 @@ pub mod solana_sky_trade {
     pub fn mint_rental_token<'info>(
         ctx: Context<'_, '_, '_, 'info, MintRentalTokenPayload<'info>>,
         land_asset_id: Pubkey,
-        creation_time: String,
+        start_time: i64,
         bump: u8,
         mint_metadata_args: Vec<u8>,
         land_asset_id_leaf_data: LeafData,
+        max_cost: u64,
     ) -> Result<()> {
         handle_mint_rental_token(
             ctx,
             land_asset_id,
-            creation_time,
+            start_time,
             bump,
             mint_metadata_args,
             land_asset_id_leaf_data,
+            max_cost,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_rental_range<'info>(
+        ctx: Context<'_, '_, '_, 'info, MintRentalTokenPayload<'info>>,
+        land_asset_id: Pubkey,
+        start_time: i64,
+        slot_count: u8,
+        bump: u8,
+        mint_metadata_args: Vec<u8>,
+        land_asset_id_leaf_data: LeafData,
+        max_cost: u64,
+    ) -> Result<()> {
+        handle_mint_rental_range(
+            ctx,
+            land_asset_id,
+            start_time,
+            slot_count,
+            bump,
+            mint_metadata_args,
+            land_asset_id_leaf_data,
+            max_cost,
         )
     }
 
@@ -45,6 +72,181 @@ pub mod solana_sky_trade {
         handle_transfer_on_expiry(ctx, leaf)
     }
 
+    pub fn settle_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, SettleBatchAccounts<'info>>,
+        leaf: LeafData,
+        proof_len: u8,
+    ) -> Result<()> {
+        handle_settle_batch(ctx, leaf, proof_len)
+    }
+
+    pub fn extend_rental<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExtendRentalAccounts<'info>>,
+        slot_count: u8,
+        land_asset_id_leaf_data: LeafData,
+        land_proof_len: u8,
+        metadata_update: Option<RentalMetadataUpdate>,
+    ) -> Result<()> {
+        handle_extend_rental(
+            ctx,
+            slot_count,
+            land_asset_id_leaf_data,
+            land_proof_len,
+            metadata_update,
+        )
+    }
+
+    pub fn end_rental_early<'info>(
+        ctx: Context<'_, '_, '_, 'info, EndRentalEarlyAccounts<'info>>,
+        land_asset_id_leaf_data: LeafData,
+        rental_leaf_data: LeafData,
+        land_proof_len: u8,
+    ) -> Result<()> {
+        handle_end_rental_early(
+            ctx,
+            land_asset_id_leaf_data,
+            rental_leaf_data,
+            land_proof_len,
+        )
+    }
+
+    pub fn transfer_rental<'info>(
+        ctx: Context<'_, '_, '_, 'info, TransferRentalAccounts<'info>>,
+        price: u64,
+        land_asset_id_leaf_data: LeafData,
+        rental_leaf_data: LeafData,
+        land_proof_len: u8,
+    ) -> Result<()> {
+        handle_transfer_rental(
+            ctx,
+            price,
+            land_asset_id_leaf_data,
+            rental_leaf_data,
+            land_proof_len,
+        )
+    }
+
+    pub fn cancel_rental<'info>(
+        ctx: Context<'_, '_, '_, 'info, CancelRentalAccounts<'info>>,
+        land_asset_id_leaf_data: LeafData,
+        rental_leaf_data: LeafData,
+        land_proof_len: u8,
+    ) -> Result<()> {
+        handle_cancel_rental(
+            ctx,
+            land_asset_id_leaf_data,
+            rental_leaf_data,
+            land_proof_len,
+        )
+    }
+
+    pub fn void_rental<'info>(
+        ctx: Context<'_, '_, '_, 'info, VoidRentalAccounts<'info>>,
+        rental_leaf: LeafData,
+        reason: u8,
+    ) -> Result<()> {
+        handle_void_rental(ctx, rental_leaf, reason)
+    }
+
+    pub fn close_day_bookings(ctx: Context<CloseDayBookingsPayload>) -> Result<()> {
+        handle_close_day_bookings(ctx)
+    }
+
+    pub fn backfill_legacy_booking(
+        ctx: Context<BackfillLegacyBookingPayload>,
+        start_time: i64,
+    ) -> Result<()> {
+        handle_backfill_legacy_booking(ctx, start_time)
+    }
+
+    pub fn open_dispute(ctx: Context<OpenDisputeAccounts>) -> Result<()> {
+        handle_open_dispute(ctx)
+    }
+
+    pub fn resolve_dispute<'info>(
+        ctx: Context<'_, '_, '_, 'info, ResolveDisputeAccounts<'info>>,
+        land_asset_id_leaf_data: LeafData,
+        renter_refund_bps: u16,
+    ) -> Result<()> {
+        handle_resolve_dispute(ctx, land_asset_id_leaf_data, renter_refund_bps)
+    }
+
+    pub fn set_land_pricing<'info>(
+        ctx: Context<'_, '_, '_, 'info, SetLandPricingAccounts<'info>>,
+        land_asset_id: Pubkey,
+        price: u64,
+        land_asset_id_leaf_data: LeafData,
+    ) -> Result<()> {
+        handle_set_land_pricing(ctx, land_asset_id, price, land_asset_id_leaf_data)
+    }
+
+    pub fn set_land_availability<'info>(
+        ctx: Context<'_, '_, '_, 'info, SetLandAvailabilityAccounts<'info>>,
+        land_asset_id: Pubkey,
+        weekly_hours: [u32; 7],
+        blackouts: Vec<Blackout>,
+        land_asset_id_leaf_data: LeafData,
+    ) -> Result<()> {
+        handle_set_land_availability(
+            ctx,
+            land_asset_id,
+            weekly_hours,
+            blackouts,
+            land_asset_id_leaf_data,
+        )
+    }
+
+    pub fn set_land_booking_mode<'info>(
+        ctx: Context<'_, '_, '_, 'info, SetLandBookingModeAccounts<'info>>,
+        land_asset_id: Pubkey,
+        approval_required: bool,
+        land_asset_id_leaf_data: LeafData,
+    ) -> Result<()> {
+        handle_set_land_booking_mode(
+            ctx,
+            land_asset_id,
+            approval_required,
+            land_asset_id_leaf_data,
+        )
+    }
+
+    pub fn approve_rental<'info>(
+        ctx: Context<'_, '_, '_, 'info, ApproveRentalAccounts<'info>>,
+        mint_metadata_args: Vec<u8>,
+        land_asset_id_leaf_data: LeafData,
+    ) -> Result<()> {
+        handle_approve_rental(ctx, mint_metadata_args, land_asset_id_leaf_data)
+    }
+
+    pub fn reject_rental<'info>(
+        ctx: Context<'_, '_, '_, 'info, RejectRentalAccounts<'info>>,
+        land_asset_id_leaf_data: LeafData,
+    ) -> Result<()> {
+        handle_reject_rental(ctx, land_asset_id_leaf_data)
+    }
+
+    pub fn expire_rental_request<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExpireRentalRequestAccounts<'info>>,
+    ) -> Result<()> {
+        handle_expire_rental_request(ctx)
+    }
+
+    pub fn set_pause(
+        ctx: Context<SetPausePayload>,
+        rentals_paused: bool,
+        settlements_paused: bool,
+    ) -> Result<()> {
+        handle_set_pause(ctx, rentals_paused, settlements_paused)
+    }
+
+    pub fn set_land_status(
+        ctx: Context<SetLandStatusPayload>,
+        land_asset_id: Pubkey,
+        suspended: bool,
+    ) -> Result<()> {
+        handle_set_land_status(ctx, land_asset_id, suspended)
+    }
+
     pub fn update_config<'info>(
         ctx: Context<'_, '_, '_, 'info, UpdateConfigPayload<'info>>,
         data: UpdateConfigData,
@@ -52,4 +254,70 @@ pub mod solana_sky_trade {
         msg!("updating config");
         handle_update_config(ctx, data)
     }
+
+    pub fn init_governance(
+        ctx: Context<InitGovernancePayload>,
+        admins: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        handle_init_governance(ctx, admins, threshold)
+    }
+
+    pub fn propose_config(
+        ctx: Context<ProposeConfigPayload>,
+        changes: UpdateConfigData,
+        pricing_changes: Option<UpdatePricingData>,
+        fee_changes: Option<UpdateFeesData>,
+        governance_changes: Option<GovernanceSettings>,
+    ) -> Result<()> {
+        handle_propose_config(
+            ctx,
+            changes,
+            pricing_changes,
+            fee_changes,
+            governance_changes,
+        )
+    }
+
+    pub fn approve_config(ctx: Context<ApproveConfigPayload>) -> Result<()> {
+        handle_approve_config(ctx)
+    }
+
+    pub fn execute_config(ctx: Context<ExecuteConfigPayload>) -> Result<()> {
+        handle_execute_config(ctx)
+    }
+
+    pub fn cancel_config(ctx: Context<CancelConfigPayload>) -> Result<()> {
+        handle_cancel_config(ctx)
+    }
+
+    pub fn propose_authority(
+        ctx: Context<ProposeAuthorityPayload>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        handle_propose_authority(ctx, new_authority)
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthorityPayload>) -> Result<()> {
+        handle_accept_authority(ctx)
+    }
+
+    pub fn cancel_authority_proposal(ctx: Context<CancelAuthorityProposalPayload>) -> Result<()> {
+        handle_cancel_authority_proposal(ctx)
+    }
+
+    pub fn update_pricing(
+        ctx: Context<UpdatePricingPayload>,
+        data: UpdatePricingData,
+    ) -> Result<()> {
+        handle_update_pricing(ctx, data)
+    }
+
+    pub fn update_fees(ctx: Context<UpdateFeesPayload>, data: UpdateFeesData) -> Result<()> {
+        handle_update_fees(ctx, data)
+    }
+
+    pub fn migrate_config(ctx: Context<MigrateConfigPayload>) -> Result<()> {
+        handle_migrate_config(ctx)
+    }
 }
@@ -2,6 +2,26 @@ use anchor_lang::prelude::Pubkey;
 
 pub const RENT_ESCROW_PREFIX: &str = "rental1";
 
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+pub const MAX_CANCELLATION_TIERS: usize = 4;
+
+pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+pub const MINUTES_PER_DAY: i64 = SECONDS_PER_DAY / 60;
+
+/// Words of the per minute bitmap of a `DayBookings`
+pub const DAY_BOOKINGS_WORDS: usize = (MINUTES_PER_DAY as usize).div_ceil(64);
+
+pub const MAX_GOVERNANCE_ADMINS: usize = 10;
+
+/// Time a config proposal can be executed for, after which it can only be cancelled
+pub const PROPOSAL_LIFETIME_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+pub const MAX_BLACKOUTS: usize = 16;
+
+/// Time a land owner has to answer a rental request, cut short by the rental start
+pub const APPROVAL_WINDOW_SECONDS: i64 = 48 * 60 * 60;
 
 #[derive(Clone)]
 pub struct MplBubblegumProgramAccount;
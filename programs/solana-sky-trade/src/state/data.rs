@@ -1,22 +1,82 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, solana_program::system_program};
+use anchor_spl::token::Mint;
+use chrono::{DateTime, Datelike, Timelike};
 use mpl_bubblegum::{hash::hash_creators, types::Creator};
 
-use crate::CustomErrors;
+use crate::{
+    bps_share, Auction, CustomErrors, BPS_DENOMINATOR, MAX_CANCELLATION_TIERS, MINUTES_PER_DAY,
+    SECONDS_PER_DAY,
+};
 
 #[account]
+#[derive(InitSpace)]
 pub struct Data {
+    /// Layout version, bumped whenever a field is added so `migrate_config` can upgrade the account
+    pub version: u8,
     pub initialized: bool,
     pub centralized_account: Pubkey,
+    /// Default price of a slot, in raw units of the configured mint
     pub base_cost: u64,
-    pub admin_quota: f64,
+    /// Share of every rental kept by the platform, in basis points
+    pub admin_quota_bps: u16,
     pub auction_house_address: Pubkey,
     pub fee_account: Pubkey,
     pub mint_address: Pubkey,
     pub land_creators: Creators,
+    /// Refund schedule applied when a renter cancels before the rental starts
+    #[max_len(MAX_CANCELLATION_TIERS)]
+    pub cancellation_tiers: Vec<CancellationTier>,
+    /// Price multiplier of each UTC hour of the day, in basis points
+    pub hour_multipliers_bps: [u16; 24],
+    /// Price multiplier of each day of the week starting on Monday, in basis points
+    pub weekday_multipliers_bps: [u16; 7],
+    /// Share of the fee paid to whoever settles an expired rental, in basis points
+    pub keeper_reward_bps: u16,
+    /// Whether the rent of settled escrows goes to the keeper instead of the fee account
+    pub rent_to_keeper: bool,
+    /// Time after a rental ends during which the renter can still dispute it, in seconds
+    pub dispute_window_seconds: i64,
+    /// Key allowed to resolve disputes besides the `centralized_account`
+    pub arbiter: Pubkey,
+    /// Key proposed to replace `centralized_account`, default while no transfer is pending
+    pub pending_authority: Pubkey,
+    /// Earliest unix timestamp at which `pending_authority` can accept
+    pub pending_authority_eta: i64,
+    /// Delay between an authority proposal and its acceptance, in seconds
+    pub authority_timelock_seconds: i64,
+    /// Whether config changes go through `Governance` proposals instead of `update_config`
+    pub config_governed: bool,
+    /// Keys entrusted with a single area each, the `centralized_account` being the config admin
+    pub roles: AdminRoles,
+    /// Blocks new rentals on every land
+    pub rentals_paused: bool,
+    /// Blocks payouts of expired rentals on every land
+    pub settlements_paused: bool,
+    /// Length of a rental slot, either a divisor of 60 or a whole number of hours
+    pub slot_minutes: u16,
+    /// How long in advance a slot can be booked, in seconds
+    pub max_advance_seconds: i64,
+    /// Minimum time between a booking and the start of its first slot, in seconds
+    pub min_lead_seconds: i64,
+    /// Whether the slot in progress can still be booked, only when `min_lead_seconds` is zero
+    pub running_slot_bookable: bool,
+    /// Share of a rental resale price paid to the land owner, in basis points
+    pub resale_royalty_bps: u16,
+    /// Rental escrows not closed yet, legacy ones included, the mint can't change while any is
+    pub open_escrows: u64,
 }
 
 impl Data {
-    pub const MAX_SIZE: usize = 8 + 1 + 32 + 8 + 8 + 32 + 32 + 32 + 32 + 32 + 32;
+    /// Current layout version
+    pub const VERSION: u8 = 1;
+
+    pub const DEFAULT_SLOT_MINUTES: u16 = 30;
+
+    /// Three months
+    pub const DEFAULT_MAX_ADVANCE_SECONDS: i64 = 90 * SECONDS_PER_DAY;
+
+    /// Space allocated to the account, discriminator included
+    pub const ACCOUNT_SIZE: usize = 8 + Data::INIT_SPACE;
 
     pub fn check_royalties_receiver(&self, received_key: Pubkey) -> Result<()> {
         require_keys_eq!(
@@ -70,11 +130,455 @@ impl Data {
 
         Ok(())
     }
+
+    /// Checks that `payment_receiver` is entitled to the rental payments of a land owned by
+    /// `land_owner`: the owner itself, or the seller when the land is listed in an auction.
+    pub fn check_payment_receiver(
+        &self,
+        land_owner: &AccountInfo,
+        payment_receiver: Pubkey,
+    ) -> Result<()> {
+        if land_owner.owner.key() == system_program::ID {
+            msg!("Land not in auction");
+            if land_owner.key() != payment_receiver {
+                return err!(CustomErrors::InvalidReceiver);
+            }
+        } else if land_owner.owner.key() == self.auction_house_address {
+            let mut auction_data: &[u8] = &land_owner.data.borrow();
+
+            let auction = Auction::try_deserialize(&mut auction_data)?;
+            msg!("Land in auction. Auction creator is {}", auction.seller);
+
+            if auction.seller != payment_receiver {
+                return err!(CustomErrors::InvalidReceiver);
+            }
+        } else {
+            return err!(CustomErrors::InvalidReceiver);
+        }
+
+        Ok(())
+    }
+
+    /// Checks the invariants every config change has to preserve
+    pub fn validate(&self) -> Result<()> {
+        let required_addresses = [
+            self.centralized_account,
+            self.auction_house_address,
+            self.fee_account,
+            self.mint_address,
+            self.land_creators.royalties_receiver,
+            self.land_creators.mint_creator,
+            self.land_creators.verification_creator,
+            self.roles.pricing_admin,
+            self.roles.fee_admin,
+            self.roles.minting_signer,
+            self.roles.pause_guardian,
+        ];
+        if required_addresses.contains(&Pubkey::default()) {
+            return err!(CustomErrors::ZeroAddress);
+        }
+
+        if self.base_cost == 0 {
+            return err!(CustomErrors::ZeroPrice);
+        }
+
+        if self.admin_quota_bps as u64 > BPS_DENOMINATOR
+            || self.keeper_reward_bps as u64 > BPS_DENOMINATOR
+            || self.resale_royalty_bps as u64 > BPS_DENOMINATOR
+        {
+            return err!(CustomErrors::BasisPointsOutOfRange);
+        }
+
+        require!(
+            self.hour_multipliers_bps
+                .iter()
+                .chain(self.weekday_multipliers_bps.iter())
+                .all(|bps| *bps > 0),
+            CustomErrors::InvalidPricingMultiplier
+        );
+
+        require!(
+            self.cancellation_tiers.len() <= MAX_CANCELLATION_TIERS
+                && self.cancellation_tiers.iter().all(|tier| {
+                    tier.min_notice_seconds >= 0 && tier.refund_bps as u64 <= BPS_DENOMINATOR
+                }),
+            CustomErrors::InvalidCancellationSchedule
+        );
+
+        require!(
+            self.dispute_window_seconds >= 0,
+            CustomErrors::InvalidDisputeWindow
+        );
+        require!(
+            self.authority_timelock_seconds >= 0,
+            CustomErrors::InvalidAuthorityTimelock
+        );
+
+        let slot_minutes = self.slot_minutes as i64;
+        require!(
+            slot_minutes > 0
+                && (60 % slot_minutes == 0
+                    || (slot_minutes % 60 == 0 && slot_minutes <= MINUTES_PER_DAY)),
+            CustomErrors::InvalidSlotLength
+        );
+        require!(
+            self.min_lead_seconds >= 0 && self.max_advance_seconds > self.min_lead_seconds,
+            CustomErrors::InvalidBookingWindow
+        );
+
+        Ok(())
+    }
+
+    /// Checks that `mint_account` is the configured mint
+    pub fn check_mint_account(&self, mint_account: &Account<Mint>) -> Result<()> {
+        require_keys_eq!(
+            self.mint_address,
+            mint_account.key(),
+            CustomErrors::MintMismatch
+        );
+        Ok(())
+    }
+
+    /// Full refund with 48h notice, half refund afterwards
+    pub fn default_cancellation_tiers() -> Vec<CancellationTier> {
+        vec![
+            CancellationTier {
+                min_notice_seconds: 48 * 60 * 60,
+                refund_bps: 10_000,
+            },
+            CancellationTier {
+                min_notice_seconds: 0,
+                refund_bps: 5_000,
+            },
+        ]
+    }
+
+    /// Returns the refund, in basis points of the rental cost, owed to a renter cancelling
+    /// `notice_seconds` before the rental starts.
+    ///
+    /// The tier with the largest `min_notice_seconds` that is still covered by the notice wins,
+    /// no matching tier means no refund.
+    pub fn cancellation_refund_bps(&self, notice_seconds: i64) -> u16 {
+        self.cancellation_tiers
+            .iter()
+            .filter(|tier| notice_seconds >= tier.min_notice_seconds)
+            .max_by_key(|tier| tier.min_notice_seconds)
+            .map_or(0, |tier| tier.refund_bps)
+    }
+
+    /// Part of the settlement `fee` paid to the keeper
+    pub fn keeper_reward(&self, fee: u64) -> Result<u64> {
+        bps_share(fee, self.keeper_reward_bps)
+    }
+
+    /// Length of a rental slot, in seconds
+    pub fn slot_seconds(&self) -> i64 {
+        self.slot_minutes as i64 * 60
+    }
+
+    /// Earliest time at which a rental ending at `end_time` can be paid out
+    pub fn settlement_time(&self, end_time: i64) -> i64 {
+        end_time.saturating_add(self.dispute_window_seconds)
+    }
+
+    /// Counts a newly opened rental escrow, see `open_escrows`
+    pub fn escrow_opened(&mut self) -> Result<()> {
+        self.open_escrows = self
+            .open_escrows
+            .checked_add(1)
+            .ok_or(CustomErrors::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Uncounts a closed rental escrow, see `open_escrows`
+    pub fn escrow_closed(&mut self) -> Result<()> {
+        self.open_escrows = self
+            .open_escrows
+            .checked_sub(1)
+            .ok_or(CustomErrors::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn check_authority(&self, received_key: Pubkey) -> Result<()> {
+        require_keys_eq!(
+            self.centralized_account,
+            received_key,
+            CustomErrors::InvalidAuthority
+        );
+        Ok(())
+    }
+
+    pub fn check_pricing_admin(&self, received_key: Pubkey) -> Result<()> {
+        require_keys_eq!(
+            self.roles.pricing_admin,
+            received_key,
+            CustomErrors::InvalidAuthority
+        );
+        Ok(())
+    }
+
+    pub fn check_fee_admin(&self, received_key: Pubkey) -> Result<()> {
+        require_keys_eq!(
+            self.roles.fee_admin,
+            received_key,
+            CustomErrors::InvalidAuthority
+        );
+        Ok(())
+    }
+
+    pub fn check_minting_signer(&self, received_key: Pubkey) -> Result<()> {
+        require_keys_eq!(
+            self.roles.minting_signer,
+            received_key,
+            CustomErrors::InvalidAuthority
+        );
+        Ok(())
+    }
+
+    /// The config admin can always act as pause guardian
+    pub fn check_pause_guardian(&self, received_key: Pubkey) -> Result<()> {
+        if received_key != self.roles.pause_guardian && received_key != self.centralized_account {
+            return err!(CustomErrors::InvalidAuthority);
+        }
+        Ok(())
+    }
+
+    pub fn check_arbiter(&self, received_key: Pubkey) -> Result<()> {
+        if received_key != self.arbiter && received_key != self.centralized_account {
+            return err!(CustomErrors::InvalidAuthority);
+        }
+        Ok(())
+    }
+
+    /// Applies the hour of day and day of week multipliers to the `price` of the slot starting
+    /// at `slot_start`, rounding down.
+    pub fn slot_price(&self, price: u64, slot_start: i64) -> Result<u64> {
+        let slot_start =
+            DateTime::from_timestamp(slot_start, 0).ok_or(CustomErrors::InvalidTimeString)?;
+        let hour_bps = self.hour_multipliers_bps[slot_start.hour() as usize];
+        let weekday_bps =
+            self.weekday_multipliers_bps[slot_start.weekday().num_days_from_monday() as usize];
+
+        bps_share(bps_share(price, hour_bps)?, weekday_bps)
+    }
+
+    /// Total price of `slot_count` consecutive slots starting at `start_time`, each priced with
+    /// the multipliers of the hour and weekday it starts in.
+    pub fn range_price(
+        &self,
+        price: u64,
+        start_time: i64,
+        slot_seconds: i64,
+        slot_count: i64,
+    ) -> Result<u64> {
+        let mut total: u64 = 0;
+        for slot in 0..slot_count {
+            let slot_cost = self.slot_price(price, start_time + slot * slot_seconds)?;
+            total = total
+                .checked_add(slot_cost)
+                .ok_or(CustomErrors::MathOverflow)?;
+        }
+        Ok(total)
+    }
 }
 
-#[derive(Clone, AnchorDeserialize, AnchorSerialize)]
+#[derive(Clone, AnchorDeserialize, AnchorSerialize, InitSpace)]
 pub struct Creators {
     pub royalties_receiver: Pubkey,
     pub mint_creator: Pubkey,
     pub verification_creator: Pubkey,
 }
+
+#[derive(Clone, AnchorDeserialize, AnchorSerialize, InitSpace)]
+pub struct AdminRoles {
+    /// Sets `base_cost`, the pricing multipliers and the cancellation schedule
+    pub pricing_admin: Pubkey,
+    /// Sets the platform quota, the fee account and the keeper incentives
+    pub fee_admin: Pubkey,
+    /// Co-signs and pays for rental mints, and delegate of the rental tree and collection
+    pub minting_signer: Pubkey,
+    pub pause_guardian: Pubkey,
+}
+
+impl AdminRoles {
+    /// Every role held by `authority`
+    pub fn all(authority: Pubkey) -> Self {
+        Self {
+            pricing_admin: authority,
+            fee_admin: authority,
+            minting_signer: authority,
+            pause_guardian: authority,
+        }
+    }
+}
+
+#[derive(Debug, Clone, AnchorDeserialize, AnchorSerialize, InitSpace)]
+pub struct CancellationTier {
+    /// Minimum time, in seconds, between the cancellation and the rental start for this tier to apply
+    pub min_notice_seconds: i64,
+    /// Share of the rental cost refunded to the renter, in basis points
+    pub refund_bps: u16,
+}
+
+/// Layout of `Data` before fees were expressed in basis points, kept to migrate the live account
+#[derive(Clone, AnchorDeserialize, AnchorSerialize)]
+pub struct LegacyData {
+    pub initialized: bool,
+    pub centralized_account: Pubkey,
+    pub base_cost: u64,
+    pub admin_quota: f64,
+    pub auction_house_address: Pubkey,
+    pub fee_account: Pubkey,
+    pub mint_address: Pubkey,
+    pub land_creators: Creators,
+}
+
+impl LegacyData {
+    /// Space allocated to the legacy account by `initialize`
+    pub const ACCOUNT_SIZE: usize = 8 + 8 + 1 + 32 + 8 + 8 + 32 + 32 + 32 + 32 + 32 + 32;
+
+    /// Converts the legacy fractional quota to basis points, rounding to the nearest one
+    pub fn admin_quota_bps(&self) -> u16 {
+        (self.admin_quota * BPS_DENOMINATOR as f64)
+            .round()
+            .clamp(0.0, BPS_DENOMINATOR as f64) as u16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data() -> Data {
+        let key = Pubkey::new_unique();
+        Data {
+            version: Data::VERSION,
+            initialized: true,
+            centralized_account: key,
+            base_cost: 1_000_000,
+            admin_quota_bps: 3_000,
+            auction_house_address: key,
+            fee_account: key,
+            mint_address: key,
+            land_creators: Creators {
+                royalties_receiver: key,
+                mint_creator: key,
+                verification_creator: key,
+            },
+            cancellation_tiers: Data::default_cancellation_tiers(),
+            hour_multipliers_bps: [BPS_DENOMINATOR as u16; 24],
+            weekday_multipliers_bps: [BPS_DENOMINATOR as u16; 7],
+            keeper_reward_bps: 0,
+            rent_to_keeper: false,
+            dispute_window_seconds: 0,
+            arbiter: key,
+            pending_authority: Pubkey::default(),
+            pending_authority_eta: 0,
+            authority_timelock_seconds: 0,
+            config_governed: false,
+            roles: AdminRoles::all(key),
+            rentals_paused: false,
+            settlements_paused: false,
+            slot_minutes: Data::DEFAULT_SLOT_MINUTES,
+            max_advance_seconds: Data::DEFAULT_MAX_ADVANCE_SECONDS,
+            min_lead_seconds: 0,
+            running_slot_bookable: false,
+            resale_royalty_bps: 0,
+            open_escrows: 0,
+        }
+    }
+
+    #[test]
+    fn cancellation_refund_bps_uses_the_largest_covered_tier() {
+        let data = data();
+
+        assert_eq!(data.cancellation_refund_bps(72 * 60 * 60), 10_000);
+        assert_eq!(data.cancellation_refund_bps(48 * 60 * 60), 10_000);
+        assert_eq!(data.cancellation_refund_bps(48 * 60 * 60 - 1), 5_000);
+        assert_eq!(data.cancellation_refund_bps(0), 5_000);
+    }
+
+    #[test]
+    fn cancellation_refund_bps_ignores_tier_order() {
+        let mut data = data();
+        data.cancellation_tiers.reverse();
+
+        assert_eq!(data.cancellation_refund_bps(72 * 60 * 60), 10_000);
+        assert_eq!(data.cancellation_refund_bps(60 * 60), 5_000);
+    }
+
+    #[test]
+    fn cancellation_refund_bps_without_covered_tier_is_zero() {
+        let mut data = data();
+        data.cancellation_tiers = vec![CancellationTier {
+            min_notice_seconds: 24 * 60 * 60,
+            refund_bps: 10_000,
+        }];
+
+        assert_eq!(data.cancellation_refund_bps(24 * 60 * 60 - 1), 0);
+
+        data.cancellation_tiers.clear();
+        assert_eq!(data.cancellation_refund_bps(72 * 60 * 60), 0);
+    }
+
+    // 2024-01-01T00:00:00Z, a Monday
+    const MONDAY: i64 = 1_704_067_200;
+
+    #[test]
+    fn range_price_without_multipliers_is_linear() {
+        let data = data();
+
+        assert_eq!(data.range_price(1_000_000, MONDAY, 1800, 0).unwrap(), 0);
+        assert_eq!(
+            data.range_price(1_000_000, MONDAY, 1800, 1).unwrap(),
+            1_000_000
+        );
+        assert_eq!(
+            data.range_price(1_000_000, MONDAY, 1800, 5).unwrap(),
+            5_000_000
+        );
+    }
+
+    #[test]
+    fn range_price_applies_the_multipliers_of_each_slot() {
+        let mut data = data();
+        data.hour_multipliers_bps[1] = 15_000;
+        data.weekday_multipliers_bps[1] = 5_000;
+
+        // 00:00 and 00:30 at the base price, 01:00 and 01:30 at 1.5x
+        assert_eq!(data.range_price(1_000, MONDAY, 1800, 4).unwrap(), 5_000);
+
+        // Tuesday 00:30 at 0.5x, then 01:00 at 1.5x * 0.5x
+        assert_eq!(
+            data.range_price(1_000, MONDAY + SECONDS_PER_DAY + 1800, 1800, 2)
+                .unwrap(),
+            500 + 750
+        );
+
+        // Sunday 23:30 rolls over to Monday 00:00
+        data.weekday_multipliers_bps[6] = 20_000;
+        assert_eq!(
+            data.range_price(1_000, MONDAY - 1800, 1800, 2).unwrap(),
+            3_000
+        );
+    }
+
+    #[test]
+    fn range_price_rounds_each_slot_down() {
+        let mut data = data();
+        data.hour_multipliers_bps = [3_333; 24];
+
+        assert_eq!(data.slot_price(10, MONDAY).unwrap(), 3);
+        assert_eq!(data.range_price(10, MONDAY, 1800, 3).unwrap(), 9);
+    }
+
+    #[test]
+    fn range_price_overflow_is_an_error() {
+        let data = data();
+
+        assert_eq!(
+            data.range_price(u64::MAX, MONDAY, 1800, 2),
+            err!(CustomErrors::MathOverflow)
+        );
+    }
+}
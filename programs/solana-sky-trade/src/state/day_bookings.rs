@@ -0,0 +1,333 @@
+use anchor_lang::prelude::*;
+
+use crate::{CustomErrors, DAY_BOOKINGS_WORDS, MINUTES_PER_DAY, SECONDS_PER_DAY};
+
+#[account]
+/// Tracks which minutes of a single UTC day are already booked for a land.
+///
+/// Bookings are tracked per minute rather than per slot, so changing `Data::slot_minutes` leaves
+/// existing bookings intact.
+pub struct DayBookings {
+    pub land_asset_id: Pubkey,
+    /// Days since the unix epoch
+    pub day: i64,
+    /// One bit per minute of the day, set while the minute is booked
+    pub booked_minutes: [u64; DAY_BOOKINGS_WORDS],
+}
+
+impl DayBookings {
+    pub const MAX_SIZE: usize = 8 + 32 + 8 + 8 * DAY_BOOKINGS_WORDS;
+
+    /// Seed for the `DayBookings` of the day `day_offset` days after the one `timestamp` falls in
+    pub fn seed_day(timestamp: i64, day_offset: i64) -> [u8; 8] {
+        (timestamp.div_euclid(SECONDS_PER_DAY) + day_offset).to_le_bytes()
+    }
+
+    fn assign(&mut self, land_asset_id: Pubkey, day: i64) -> Result<()> {
+        // Freshly created accounts are zeroed
+        if self.land_asset_id == Pubkey::default() {
+            self.land_asset_id = land_asset_id;
+            self.day = day;
+        }
+        require_keys_eq!(
+            self.land_asset_id,
+            land_asset_id,
+            CustomErrors::InvalidDayBookings
+        );
+        require_eq!(self.day, day, CustomErrors::InvalidDayBookings);
+        Ok(())
+    }
+
+    fn book(&mut self, first_minute: i64, end_minute: i64) -> Result<()> {
+        if minute_masks(first_minute, end_minute)
+            .any(|(word, mask)| self.booked_minutes[word] & mask != 0)
+        {
+            return err!(CustomErrors::SlotAlreadyBooked);
+        }
+        for (word, mask) in minute_masks(first_minute, end_minute) {
+            self.booked_minutes[word] |= mask;
+        }
+        Ok(())
+    }
+
+    fn release(&mut self, first_minute: i64, end_minute: i64) {
+        for (word, mask) in minute_masks(first_minute, end_minute) {
+            self.booked_minutes[word] &= !mask;
+        }
+    }
+}
+
+/// Masks of the minutes `first_minute..end_minute` of a day, as `(word, mask)` pairs. An empty
+/// range has no mask.
+fn minute_masks(first_minute: i64, end_minute: i64) -> impl Iterator<Item = (usize, u64)> {
+    let words = if first_minute < end_minute {
+        first_minute / 64..(end_minute + 63) / 64
+    } else {
+        0..0
+    };
+    words.map(move |word| {
+        let low = first_minute.max(word * 64) - word * 64;
+        let high = end_minute.min(word * 64 + 64) - word * 64;
+        (word as usize, (u64::MAX >> (64 - (high - low))) << low)
+    })
+}
+
+/// Splits the booking `start_timestamp..end_timestamp` into the minute ranges of the start day
+/// and of the following one.
+fn day_ranges(start_timestamp: i64, end_timestamp: i64) -> (i64, (i64, i64), (i64, i64)) {
+    let day = start_timestamp.div_euclid(SECONDS_PER_DAY);
+    let day_start = day * SECONDS_PER_DAY;
+    let first_minute = (start_timestamp - day_start) / 60;
+    let end_minute = (end_timestamp - day_start) / 60;
+
+    (
+        day,
+        (first_minute, end_minute.min(MINUTES_PER_DAY)),
+        (0, (end_minute - MINUTES_PER_DAY).max(0)),
+    )
+}
+
+/// Marks the minutes of a booking as taken, failing if any of them is already booked.
+pub fn book_slots(
+    day_bookings: &mut DayBookings,
+    next_day_bookings: Option<&mut DayBookings>,
+    land_asset_id: Pubkey,
+    start_timestamp: i64,
+    end_timestamp: i64,
+) -> Result<()> {
+    let (day, (first, end), (next_first, next_end)) = day_ranges(start_timestamp, end_timestamp);
+
+    day_bookings.assign(land_asset_id, day)?;
+    day_bookings.book(first, end)?;
+
+    if next_end > next_first {
+        let next_day_bookings = next_day_bookings.ok_or(CustomErrors::MissingNextDayBookings)?;
+        next_day_bookings.assign(land_asset_id, day + 1)?;
+        next_day_bookings.book(next_first, next_end)?;
+    }
+
+    Ok(())
+}
+
+/// Frees the minutes of a booking so they can be booked again.
+pub fn release_slots(
+    day_bookings: &mut DayBookings,
+    next_day_bookings: Option<&mut DayBookings>,
+    land_asset_id: Pubkey,
+    start_timestamp: i64,
+    end_timestamp: i64,
+) -> Result<()> {
+    let (day, (first, end), (next_first, next_end)) = day_ranges(start_timestamp, end_timestamp);
+
+    day_bookings.assign(land_asset_id, day)?;
+    day_bookings.release(first, end);
+
+    if next_end > next_first {
+        let next_day_bookings = next_day_bookings.ok_or(CustomErrors::MissingNextDayBookings)?;
+        next_day_bookings.assign(land_asset_id, day + 1)?;
+        next_day_bookings.release(next_first, next_end);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: i64 = 19_723;
+    const MIDNIGHT: i64 = DAY * SECONDS_PER_DAY;
+
+    fn bookings() -> DayBookings {
+        DayBookings {
+            land_asset_id: Pubkey::default(),
+            day: 0,
+            booked_minutes: [0; DAY_BOOKINGS_WORDS],
+        }
+    }
+
+    fn booked(bookings: &DayBookings) -> Vec<i64> {
+        (0..MINUTES_PER_DAY)
+            .filter(|minute| {
+                bookings.booked_minutes[*minute as usize / 64] >> (minute % 64) & 1 == 1
+            })
+            .collect()
+    }
+
+    #[test]
+    fn minute_masks_split_ranges_across_words() {
+        assert_eq!(minute_masks(0, 64).collect::<Vec<_>>(), vec![(0, u64::MAX)]);
+        assert_eq!(minute_masks(3, 5).collect::<Vec<_>>(), vec![(0, 0b11000)]);
+        assert_eq!(
+            minute_masks(60, 70).collect::<Vec<_>>(),
+            vec![(0, 0b1111 << 60), (1, 0b111111)]
+        );
+        assert_eq!(
+            minute_masks(MINUTES_PER_DAY - 30, MINUTES_PER_DAY).collect::<Vec<_>>(),
+            vec![(22, (u64::MAX >> 34) << 2)]
+        );
+    }
+
+    #[test]
+    fn minute_masks_of_an_empty_range_are_empty() {
+        assert_eq!(minute_masks(600, 600).count(), 0);
+        assert_eq!(minute_masks(640, 640).count(), 0);
+        assert_eq!(minute_masks(MINUTES_PER_DAY, MINUTES_PER_DAY).count(), 0);
+        assert_eq!(minute_masks(70, 60).count(), 0);
+
+        let land = Pubkey::new_unique();
+        let mut day_bookings = bookings();
+        book_slots(&mut day_bookings, None, land, MIDNIGHT, MIDNIGHT + 36_000).unwrap();
+        release_slots(
+            &mut day_bookings,
+            None,
+            land,
+            MIDNIGHT + 36_000,
+            MIDNIGHT + 36_000,
+        )
+        .unwrap();
+        assert_eq!(booked(&day_bookings), (0..600).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn book_slots_rejects_overlapping_bookings() {
+        let land = Pubkey::new_unique();
+        let mut day_bookings = bookings();
+
+        book_slots(
+            &mut day_bookings,
+            None,
+            land,
+            MIDNIGHT + 3600,
+            MIDNIGHT + 7200,
+        )
+        .unwrap();
+        assert_eq!(day_bookings.land_asset_id, land);
+        assert_eq!(day_bookings.day, DAY);
+        assert_eq!(booked(&day_bookings), (60..120).collect::<Vec<_>>());
+
+        assert_eq!(
+            book_slots(
+                &mut day_bookings,
+                None,
+                land,
+                MIDNIGHT + 5400,
+                MIDNIGHT + 9000
+            ),
+            err!(CustomErrors::SlotAlreadyBooked)
+        );
+        // A failed booking leaves the day untouched
+        assert_eq!(booked(&day_bookings), (60..120).collect::<Vec<_>>());
+
+        book_slots(
+            &mut day_bookings,
+            None,
+            land,
+            MIDNIGHT + 7200,
+            MIDNIGHT + 9000,
+        )
+        .unwrap();
+        assert_eq!(booked(&day_bookings), (60..150).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn release_slots_allows_booking_again() {
+        let land = Pubkey::new_unique();
+        let mut day_bookings = bookings();
+
+        book_slots(
+            &mut day_bookings,
+            None,
+            land,
+            MIDNIGHT + 3600,
+            MIDNIGHT + 7200,
+        )
+        .unwrap();
+        release_slots(
+            &mut day_bookings,
+            None,
+            land,
+            MIDNIGHT + 3600,
+            MIDNIGHT + 7200,
+        )
+        .unwrap();
+        assert!(booked(&day_bookings).is_empty());
+
+        book_slots(
+            &mut day_bookings,
+            None,
+            land,
+            MIDNIGHT + 5400,
+            MIDNIGHT + 9000,
+        )
+        .unwrap();
+        assert_eq!(booked(&day_bookings), (90..150).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn book_slots_across_midnight_requires_the_next_day() {
+        let land = Pubkey::new_unique();
+        let start = MIDNIGHT + SECONDS_PER_DAY - 1800;
+        let end = MIDNIGHT + SECONDS_PER_DAY + 3600;
+        let mut day_bookings = bookings();
+        let mut next_day_bookings = bookings();
+
+        assert_eq!(
+            book_slots(&mut bookings(), None, land, start, end),
+            err!(CustomErrors::MissingNextDayBookings)
+        );
+
+        book_slots(
+            &mut day_bookings,
+            Some(&mut next_day_bookings),
+            land,
+            start,
+            end,
+        )
+        .unwrap();
+        assert_eq!(
+            booked(&day_bookings),
+            (MINUTES_PER_DAY - 30..MINUTES_PER_DAY).collect::<Vec<_>>()
+        );
+        assert_eq!(next_day_bookings.day, DAY + 1);
+        assert_eq!(booked(&next_day_bookings), (0..60).collect::<Vec<_>>());
+
+        // Ending exactly at midnight stays within the day
+        book_slots(
+            &mut bookings(),
+            None,
+            land,
+            start,
+            MIDNIGHT + SECONDS_PER_DAY,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn book_slots_rejects_bookings_of_another_land_or_day() {
+        let land = Pubkey::new_unique();
+        let mut day_bookings = bookings();
+        book_slots(&mut day_bookings, None, land, MIDNIGHT, MIDNIGHT + 1800).unwrap();
+
+        assert_eq!(
+            book_slots(
+                &mut day_bookings,
+                None,
+                Pubkey::new_unique(),
+                MIDNIGHT + 3600,
+                MIDNIGHT + 5400
+            ),
+            err!(CustomErrors::InvalidDayBookings)
+        );
+        assert_eq!(
+            release_slots(
+                &mut day_bookings,
+                None,
+                land,
+                MIDNIGHT + SECONDS_PER_DAY,
+                MIDNIGHT + SECONDS_PER_DAY + 1800
+            ),
+            err!(CustomErrors::InvalidDayBookings)
+        );
+    }
+}
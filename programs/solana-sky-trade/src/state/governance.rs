@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    CustomErrors, Data, UpdateConfigData, UpdateFeesData, UpdatePricingData, MAX_GOVERNANCE_ADMINS,
+    PROPOSAL_LIFETIME_SECONDS,
+};
+
+#[account]
+/// M-of-N admin set that approves config, pricing and fee changes once `Data::config_governed`
+/// is set
+pub struct Governance {
+    pub admins: Vec<Pubkey>,
+    /// Number of admin approvals a proposal needs before it can be executed
+    pub threshold: u8,
+    /// Id of the next proposal, used as its seed
+    pub proposal_count: u64,
+    pub bump: u8,
+}
+
+impl Governance {
+    pub const MAX_SIZE: usize = 8 + 4 + 32 * MAX_GOVERNANCE_ADMINS + 1 + 8 + 1;
+
+    pub fn check_settings(admins: &[Pubkey], threshold: u8) -> Result<()> {
+        let distinct = admins
+            .iter()
+            .enumerate()
+            .all(|(index, admin)| !admins[..index].contains(admin));
+
+        require!(
+            !admins.is_empty()
+                && admins.len() <= MAX_GOVERNANCE_ADMINS
+                && distinct
+                && threshold > 0
+                && threshold as usize <= admins.len(),
+            CustomErrors::InvalidGovernanceSettings
+        );
+        Ok(())
+    }
+
+    pub fn check_admin(&self, received_key: Pubkey) -> Result<()> {
+        if !self.admins.contains(&received_key) {
+            return err!(CustomErrors::NotGovernanceAdmin);
+        }
+        Ok(())
+    }
+
+    /// Approvals of a proposal that still count, admins removed since don't
+    pub fn count_approvals(&self, proposal: &ConfigProposal) -> usize {
+        proposal
+            .approvals
+            .iter()
+            .filter(|admin| self.admins.contains(admin))
+            .count()
+    }
+}
+
+/// New admin set and threshold of the governance
+#[derive(Debug, Clone, AnchorDeserialize, AnchorSerialize)]
+pub struct GovernanceSettings {
+    pub admins: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+impl GovernanceSettings {
+    pub const MAX_SIZE: usize = 4 + 32 * MAX_GOVERNANCE_ADMINS + 1;
+}
+
+#[account]
+/// Config change queued until enough governance admins approve it
+pub struct ConfigProposal {
+    pub id: u64,
+    /// Admin that created the proposal, refunded when it is executed
+    pub proposer: Pubkey,
+    pub changes: UpdateConfigData,
+    /// Admins that approved so far, including the proposer
+    pub approvals: Vec<Pubkey>,
+    pub bump: u8,
+    /// Settings otherwise owned by the pricing admin
+    pub pricing_changes: Option<UpdatePricingData>,
+    /// Settings otherwise owned by the fee admin
+    pub fee_changes: Option<UpdateFeesData>,
+    /// Admins and threshold the governance switches to
+    pub governance_changes: Option<GovernanceSettings>,
+    /// Unix timestamp at which the proposal was created, see `PROPOSAL_LIFETIME_SECONDS`
+    pub created_at: i64,
+}
+
+impl ConfigProposal {
+    pub const MAX_SIZE: usize = 8
+        + 8
+        + 32
+        + UpdateConfigData::MAX_SIZE
+        + 4
+        + 32 * MAX_GOVERNANCE_ADMINS
+        + 1
+        + 1
+        + UpdatePricingData::MAX_SIZE
+        + 1
+        + UpdateFeesData::MAX_SIZE
+        + 1
+        + GovernanceSettings::MAX_SIZE
+        + 8;
+
+    pub fn is_expired(&self, current_timestamp: i64) -> bool {
+        current_timestamp >= self.created_at + PROPOSAL_LIFETIME_SECONDS
+    }
+
+    /// Writes every change of the proposal to `data`, then validates the resulting config
+    pub fn apply(&self, data: &mut Data) -> Result<()> {
+        self.changes.clone().apply(data)?;
+        if let Some(pricing_changes) = self.pricing_changes.clone() {
+            pricing_changes.apply(data)?;
+        }
+        if let Some(fee_changes) = self.fee_changes.clone() {
+            fee_changes.apply(data)?;
+        }
+        Ok(())
+    }
+
+    pub fn approve(&mut self, admin: Pubkey) -> Result<()> {
+        if self.approvals.contains(&admin) {
+            return err!(CustomErrors::ProposalAlreadyApproved);
+        }
+        self.approvals.push(admin);
+        Ok(())
+    }
+}
@@ -0,0 +1,220 @@
+use anchor_lang::prelude::*;
+use chrono::{DateTime, Datelike};
+
+use crate::{CustomErrors, MAX_BLACKOUTS, SECONDS_PER_DAY};
+
+#[account]
+#[derive(InitSpace)]
+/// Times at which a land can be rented, set by the land owner
+pub struct LandAvailability {
+    pub land_asset_id: Pubkey,
+    /// Land owner that set the availability, the land is always available once it changes hands
+    pub owner: Pubkey,
+    /// Opening hours of each day of the week starting on Monday, one bit per UTC hour
+    pub weekly_hours: [u32; 7],
+    /// Periods during which the land can't be rented, whatever the opening hours
+    #[max_len(MAX_BLACKOUTS)]
+    pub blackouts: Vec<Blackout>,
+    pub bump: u8,
+}
+
+#[derive(Debug, Clone, AnchorDeserialize, AnchorSerialize, InitSpace)]
+pub struct Blackout {
+    /// Unix timestamp at which the blackout starts
+    pub start_time: i64,
+    /// Unix timestamp at which the blackout ends, excluded
+    pub end_time: i64,
+}
+
+impl LandAvailability {
+    pub const MAX_SIZE: usize = 8 + LandAvailability::INIT_SPACE;
+
+    /// Opening hours of a day open around the clock
+    pub const ALL_DAY: u32 = (1 << 24) - 1;
+
+    pub fn check_settings(weekly_hours: &[u32; 7], blackouts: &[Blackout]) -> Result<()> {
+        require!(
+            weekly_hours.iter().all(|hours| *hours <= Self::ALL_DAY)
+                && blackouts.len() <= MAX_BLACKOUTS
+                && blackouts
+                    .iter()
+                    .all(|blackout| blackout.start_time < blackout.end_time),
+            CustomErrors::InvalidAvailability
+        );
+        Ok(())
+    }
+
+    /// Returns whether the land is open during every hour touched by `start_time..end_time` and
+    /// that period doesn't overlap any blackout.
+    pub fn is_open(&self, start_time: i64, end_time: i64) -> Result<bool> {
+        if self
+            .blackouts
+            .iter()
+            .any(|blackout| blackout.start_time < end_time && start_time < blackout.end_time)
+        {
+            return Ok(false);
+        }
+
+        let mut hour_start = start_time - start_time.rem_euclid(3600);
+        while hour_start < end_time {
+            let hour =
+                DateTime::from_timestamp(hour_start, 0).ok_or(CustomErrors::InvalidTimeString)?;
+            let weekday = hour.weekday().num_days_from_monday() as usize;
+            let hour_of_day = hour_start.rem_euclid(SECONDS_PER_DAY) / 3600;
+            if self.weekly_hours[weekday] & (1 << hour_of_day) == 0 {
+                return Ok(false);
+            }
+            hour_start += 3600;
+        }
+
+        Ok(true)
+    }
+
+    /// Returns whether the land owned by `land_owner` can be rented from `start_time` to
+    /// `end_time`.
+    ///
+    /// `land_availability` is the `LandAvailability` PDA of the land, which may not have been
+    /// created yet, in which case the land is always available.
+    pub fn is_available(
+        land_availability: &AccountInfo,
+        land_owner: Pubkey,
+        start_time: i64,
+        end_time: i64,
+    ) -> Result<bool> {
+        if land_availability.owner != &crate::ID || land_availability.data_is_empty() {
+            return Ok(true);
+        }
+
+        let mut data: &[u8] = &land_availability.try_borrow_data()?;
+        let availability = LandAvailability::try_deserialize(&mut data)?;
+
+        if availability.owner != land_owner {
+            return Ok(true);
+        }
+        availability.is_open(start_time, end_time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2024-01-01T00:00:00Z, a Monday
+    const MONDAY: i64 = 1_704_067_200;
+    const HOUR: i64 = 3600;
+
+    fn availability(weekly_hours: [u32; 7], blackouts: Vec<Blackout>) -> LandAvailability {
+        LandAvailability {
+            land_asset_id: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            weekly_hours,
+            blackouts,
+            bump: 0,
+        }
+    }
+
+    // Open from 09:00 to 17:00
+    const OFFICE_HOURS: u32 = 0xff << 9;
+
+    #[test]
+    fn is_open_follows_the_opening_hours_of_each_weekday() {
+        let mut weekly_hours = [LandAvailability::ALL_DAY; 7];
+        weekly_hours[0] = OFFICE_HOURS;
+        weekly_hours[6] = 0;
+        let availability = availability(weekly_hours, vec![]);
+
+        assert!(availability
+            .is_open(MONDAY + 9 * HOUR, MONDAY + 17 * HOUR)
+            .unwrap());
+        assert!(!availability
+            .is_open(MONDAY + 8 * HOUR, MONDAY + 9 * HOUR)
+            .unwrap());
+        assert!(!availability
+            .is_open(MONDAY + 17 * HOUR, MONDAY + 18 * HOUR)
+            .unwrap());
+        // Every hour touched by the rental has to be open
+        assert!(!availability
+            .is_open(MONDAY + 16 * HOUR + 1800, MONDAY + 17 * HOUR + 1800)
+            .unwrap());
+
+        // Sunday is closed, Tuesday open all day
+        assert!(!availability.is_open(MONDAY - HOUR, MONDAY).unwrap());
+        assert!(availability
+            .is_open(MONDAY + 24 * HOUR, MONDAY + 48 * HOUR)
+            .unwrap());
+    }
+
+    #[test]
+    fn is_open_across_midnight_checks_both_days() {
+        let mut weekly_hours = [LandAvailability::ALL_DAY; 7];
+        weekly_hours[1] = OFFICE_HOURS;
+        let availability = availability(weekly_hours, vec![]);
+
+        assert!(!availability
+            .is_open(MONDAY + 23 * HOUR, MONDAY + 25 * HOUR)
+            .unwrap());
+        assert!(availability
+            .is_open(MONDAY + 23 * HOUR, MONDAY + 24 * HOUR)
+            .unwrap());
+    }
+
+    #[test]
+    fn is_open_is_false_during_blackouts() {
+        let blackout = Blackout {
+            start_time: MONDAY + 10 * HOUR,
+            end_time: MONDAY + 12 * HOUR,
+        };
+        let availability = availability([LandAvailability::ALL_DAY; 7], vec![blackout]);
+
+        assert!(!availability
+            .is_open(MONDAY + 11 * HOUR, MONDAY + 13 * HOUR)
+            .unwrap());
+        assert!(!availability
+            .is_open(MONDAY + 9 * HOUR, MONDAY + 13 * HOUR)
+            .unwrap());
+        // The blackout end is excluded
+        assert!(availability
+            .is_open(MONDAY + 12 * HOUR, MONDAY + 13 * HOUR)
+            .unwrap());
+        assert!(availability
+            .is_open(MONDAY + 9 * HOUR, MONDAY + 10 * HOUR)
+            .unwrap());
+    }
+
+    #[test]
+    fn check_settings_rejects_invalid_hours_and_blackouts() {
+        let blackouts = vec![
+            Blackout {
+                start_time: MONDAY,
+                end_time: MONDAY + HOUR,
+            };
+            MAX_BLACKOUTS + 1
+        ];
+        LandAvailability::check_settings(
+            &[LandAvailability::ALL_DAY; 7],
+            &blackouts[..MAX_BLACKOUTS],
+        )
+        .unwrap();
+
+        let mut weekly_hours = [0; 7];
+        weekly_hours[3] = 1 << 24;
+        assert_eq!(
+            LandAvailability::check_settings(&weekly_hours, &[]),
+            err!(CustomErrors::InvalidAvailability)
+        );
+
+        let empty = Blackout {
+            start_time: MONDAY,
+            end_time: MONDAY,
+        };
+        assert_eq!(
+            LandAvailability::check_settings(&[0; 7], &[empty]),
+            err!(CustomErrors::InvalidAvailability)
+        );
+
+        assert_eq!(
+            LandAvailability::check_settings(&[0; 7], &blackouts),
+            err!(CustomErrors::InvalidAvailability)
+        );
+    }
+}
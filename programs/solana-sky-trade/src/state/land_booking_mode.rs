@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+#[account]
+/// How rentals of a land are confirmed, set by the land owner
+pub struct LandBookingMode {
+    pub land_asset_id: Pubkey,
+    /// Land owner that set the mode, the land is back to instant bookings once it changes hands
+    pub owner: Pubkey,
+    /// Whether rentals wait for `approve_rental` before the rental cNFT is minted
+    pub approval_required: bool,
+    pub bump: u8,
+}
+
+impl LandBookingMode {
+    pub const MAX_SIZE: usize = 8 + 32 + 32 + 1 + 1;
+
+    /// Returns whether `land_owner` requires rentals of the land to be approved.
+    ///
+    /// `land_booking_mode` is the `LandBookingMode` PDA of the land, which may not have been
+    /// created yet.
+    pub fn requires_approval(land_booking_mode: &AccountInfo, land_owner: Pubkey) -> Result<bool> {
+        if land_booking_mode.owner != &crate::ID || land_booking_mode.data_is_empty() {
+            return Ok(false);
+        }
+
+        let mut data: &[u8] = &land_booking_mode.try_borrow_data()?;
+        let mode = LandBookingMode::try_deserialize(&mut data)?;
+
+        Ok(mode.owner == land_owner && mode.approval_required)
+    }
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+#[account]
+/// Rental price of a single slot of a land, set by the land owner
+pub struct LandPricing {
+    pub land_asset_id: Pubkey,
+    /// Land owner that set the price, the price is ignored once the land changes hands
+    pub owner: Pubkey,
+    /// Price of a slot, in raw units of the configured mint
+    pub price: u64,
+    pub bump: u8,
+}
+
+impl LandPricing {
+    pub const MAX_SIZE: usize = 8 + 32 + 32 + 8 + 1;
+
+    /// Returns the price set by `land_owner` for the land, if any.
+    ///
+    /// `land_pricing` is the `LandPricing` PDA of the land, which may not have been created yet.
+    pub fn price_for(land_pricing: &AccountInfo, land_owner: Pubkey) -> Result<Option<u64>> {
+        if land_pricing.owner != &crate::ID || land_pricing.data_is_empty() {
+            return Ok(None);
+        }
+
+        let mut data: &[u8] = &land_pricing.try_borrow_data()?;
+        let pricing = LandPricing::try_deserialize(&mut data)?;
+
+        Ok((pricing.owner == land_owner).then_some(pricing.price))
+    }
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+#[account]
+/// Admin controlled status of a single land
+pub struct LandStatus {
+    pub land_asset_id: Pubkey,
+    /// Blocks new rentals and settlements of the land while set
+    pub suspended: bool,
+    pub bump: u8,
+}
+
+impl LandStatus {
+    pub const MAX_SIZE: usize = 8 + 32 + 1 + 1;
+
+    /// Returns whether the land is suspended.
+    ///
+    /// `land_status` is the `LandStatus` PDA of the land, which may not have been created yet.
+    pub fn is_suspended(land_status: &AccountInfo) -> Result<bool> {
+        if land_status.owner != &crate::ID || land_status.data_is_empty() {
+            return Ok(false);
+        }
+
+        let mut data: &[u8] = &land_status.try_borrow_data()?;
+        let status = LandStatus::try_deserialize(&mut data)?;
+
+        Ok(status.suspended)
+    }
+}
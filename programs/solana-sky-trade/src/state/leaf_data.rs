@@ -1,4 +1,8 @@
 use anchor_lang::prelude::*;
+use mpl_bubblegum::{
+    instructions::{VerifyLeafCpi, VerifyLeafCpiAccounts, VerifyLeafInstructionArgs},
+    types::LeafSchema,
+};
 
 #[derive(Clone, AnchorDeserialize, AnchorSerialize)]
 pub struct LeafData {
@@ -10,6 +14,42 @@ pub struct LeafData {
     // pub metadata: AnchorMetadataArgs,
 }
 
+impl LeafData {
+    /// Makes a CPI to verify that the leaf of `asset_id`, owned by `owner` and delegated to
+    /// `delegate`, is valid data in the `merkle_tree`
+    pub fn verify_leaf<'info>(
+        &self,
+        compression_program: &AccountInfo<'info>,
+        merkle_tree: &AccountInfo<'info>,
+        asset_id: Pubkey,
+        owner: Pubkey,
+        delegate: Pubkey,
+        proof: &[(&AccountInfo<'info>, bool, bool)],
+    ) -> Result<()> {
+        let leaf = LeafSchema::V1 {
+            id: asset_id,
+            owner,
+            delegate,
+            nonce: self.nonce,
+            data_hash: self.hash,
+            creator_hash: self.creator_hash,
+        };
+
+        VerifyLeafCpi::new(
+            compression_program,
+            VerifyLeafCpiAccounts { merkle_tree },
+            VerifyLeafInstructionArgs {
+                index: self.index,
+                root: self.root,
+                leaf: leaf.hash(),
+            },
+        )
+        .invoke_with_remaining_accounts(proof)?;
+
+        Ok(())
+    }
+}
+
 // #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 // pub struct AnchorMetadataArgs {
 //     pub name: String,
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::{CustomErrors, BPS_DENOMINATOR};
+
+/// Returns `bps` basis points of `amount`.
+///
+/// Shares are always rounded down, so any dust stays with the party receiving the remainder
+/// (the land owner on payouts, the platform on refunds).
+pub fn bps_share(amount: u64, bps: u16) -> Result<u64> {
+    let share = (amount as u128)
+        .checked_mul(bps as u128)
+        .ok_or(CustomErrors::MathOverflow)?
+        / BPS_DENOMINATOR as u128;
+
+    u64::try_from(share).map_err(|_| error!(CustomErrors::MathOverflow))
+}
+
+/// Returns the `part / total` share of `amount`, rounded down like `bps_share`
+pub fn pro_rata_share(amount: u64, part: u64, total: u64) -> Result<u64> {
+    let share = (amount as u128)
+        .checked_mul(part as u128)
+        .ok_or(CustomErrors::MathOverflow)?
+        .checked_div(total as u128)
+        .ok_or(CustomErrors::MathOverflow)?;
+
+    u64::try_from(share).map_err(|_| error!(CustomErrors::MathOverflow))
+}
@@ -1,11 +1,25 @@
 pub mod auction;
 pub mod constant;
 pub mod data;
+pub mod day_bookings;
+pub mod governance;
+pub mod land_availability;
+pub mod land_booking_mode;
+pub mod land_pricing;
+pub mod land_status;
 pub mod leaf_data;
+pub mod math;
 pub mod rent_escrow;
 
 pub use auction::*;
 pub use constant::*;
 pub use data::*;
+pub use day_bookings::*;
+pub use governance::*;
+pub use land_availability::*;
+pub use land_booking_mode::*;
+pub use land_pricing::*;
+pub use land_status::*;
 pub use leaf_data::*;
+pub use math::*;
 pub use rent_escrow::*;
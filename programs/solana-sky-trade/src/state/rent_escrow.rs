@@ -1,6 +1,70 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, system_program, Discriminator};
+use chrono::DateTime;
+
+use crate::CustomErrors;
+
+#[account]
+/// Holds the payment of a rental until it is settled, refunded or cancelled
+pub struct RentalEscrow {
+    pub land_asset_id: Pubkey,
+    /// Unix timestamp at which the first booked slot starts
+    pub start_time: i64,
+    /// Unix timestamp at which the last booked slot ends
+    pub end_time: i64,
+    /// Length of a slot at booking time, in seconds
+    pub slot_seconds: i64,
+    /// Seed of the escrow, the start minute `start_time / 60`
+    pub slot_index_bytes: [u8; 8],
+    pub expected_cost: u64,
+    pub fee_quota: u64,
+    pub escrow_bump: [u8; 1],
+    /// Wallet that paid for the rental and owns the rental cNFT
+    pub renter: Pubkey,
+    /// Asset id of the rental cNFT minted alongside this escrow
+    pub rental_asset_id: Pubkey,
+    /// Set by the renter to hold the payout until an arbiter resolves the dispute
+    pub disputed: bool,
+    /// Time until which the land owner can approve the rental request, zero for confirmed
+    /// rentals
+    pub approval_deadline: i64,
+    /// Layout version, escrows of the legacy `RentEscrow` layout stay settleable
+    pub version: u8,
+    /// Token account the rental is paid from, refunds and resale proceeds can only go to it. Moves
+    /// to the new renter on transfer.
+    pub renter_ata: Pubkey,
+    /// Tree holding the rental cNFT, set once it is minted
+    pub rental_merkle_tree: Pubkey,
+}
+
+impl RentalEscrow {
+    /// Current layout version
+    pub const VERSION: u8 = 1;
+
+    pub const MAX_SIZE: usize = 8 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 32 + 32 + 1 + 8 + 1 + 32 + 32;
+
+    pub fn escrow_seeds(&self) -> [&[u8]; 4] {
+        [
+            b"rental_escrow",
+            self.land_asset_id.as_ref(),
+            &self.slot_index_bytes,
+            &self.escrow_bump,
+        ]
+    }
+
+    /// Whether the rental is still waiting for the land owner approval
+    pub fn is_pending(&self) -> bool {
+        self.approval_deadline != 0
+    }
+
+    pub fn slot_count(&self) -> i64 {
+        (self.end_time - self.start_time) / self.slot_seconds
+    }
+}
 
 #[account]
+/// Legacy escrow layout, with RFC3339 times. No longer created, but still settleable.
+///
+/// Only the leading fields shared by every legacy version are declared.
 pub struct RentEscrow {
     pub land_asset_id: Pubkey,
     pub creation_time: String,
@@ -11,14 +75,87 @@ pub struct RentEscrow {
 }
 
 impl RentEscrow {
-    pub const MAX_SIZE: usize = 8 + 32 + 28 + 28 + 8 + 8 + 1;
-
     pub fn escrow_seeds(&self) -> [&[u8]; 4] {
         [
             b"escrow",
-            &self.land_asset_id.as_ref(),
-            &self.creation_time.as_ref(),
+            self.land_asset_id.as_ref(),
+            self.creation_time.as_ref(),
             &self.escrow_bump,
         ]
     }
 }
+
+/// What settling an escrow requires, read from either the current or the legacy layout
+pub struct SettlementTerms {
+    pub land_asset_id: Pubkey,
+    pub end_time: i64,
+    pub expected_cost: u64,
+    pub fee_quota: u64,
+    pub disputed: bool,
+    pub pending: bool,
+    seeds: Vec<Vec<u8>>,
+}
+
+impl SettlementTerms {
+    pub fn load(escrow: &AccountInfo) -> Result<Self> {
+        if escrow.owner != &crate::ID {
+            return err!(ErrorCode::AccountOwnedByWrongProgram);
+        }
+
+        let data = escrow.try_borrow_data()?;
+        if data.len() < 8 {
+            return err!(ErrorCode::AccountDiscriminatorNotFound);
+        }
+
+        if data[..8] == RentalEscrow::DISCRIMINATOR {
+            let escrow = RentalEscrow::try_deserialize(&mut &data[..])?;
+            Ok(Self {
+                land_asset_id: escrow.land_asset_id,
+                end_time: escrow.end_time,
+                expected_cost: escrow.expected_cost,
+                fee_quota: escrow.fee_quota,
+                disputed: escrow.disputed,
+                pending: escrow.is_pending(),
+                seeds: escrow.escrow_seeds().map(<[u8]>::to_vec).to_vec(),
+            })
+        } else if data[..8] == RentEscrow::DISCRIMINATOR {
+            let escrow = RentEscrow::try_deserialize(&mut &data[..])?;
+            let end_time = DateTime::parse_from_rfc3339(&escrow.end_time)
+                .map_err(|_| error!(CustomErrors::InvalidTimeString))?
+                .timestamp();
+            Ok(Self {
+                land_asset_id: escrow.land_asset_id,
+                end_time,
+                expected_cost: escrow.expected_cost,
+                fee_quota: escrow.fee_quota,
+                disputed: false,
+                pending: false,
+                seeds: escrow.escrow_seeds().map(<[u8]>::to_vec).to_vec(),
+            })
+        } else {
+            err!(ErrorCode::AccountDiscriminatorMismatch)
+        }
+    }
+
+    pub fn signer_seeds(&self) -> Vec<&[u8]> {
+        self.seeds.iter().map(Vec::as_slice).collect()
+    }
+}
+
+/// Closes a program owned account, sending its lamports to `destination`
+pub fn close_program_account<'info>(
+    account: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+) -> Result<()> {
+    let lamports = account.lamports();
+    **destination.try_borrow_mut_lamports()? = destination
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(CustomErrors::MathOverflow)?;
+    **account.try_borrow_mut_lamports()? = 0;
+
+    account.assign(&system_program::ID);
+    account.realloc(0, false)?;
+
+    Ok(())
+}
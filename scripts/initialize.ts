@@ -82,6 +82,10 @@ import {
     [Buffer.from("central_authority")],
     program.programId
   )[0];
+  const eventAuthority = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("__event_authority")],
+    program.programId
+  )[0];
 
   // input mint account
   const mintAccount = new anchor.web3.PublicKey(
@@ -240,6 +244,8 @@ import {
         centralAuthority: centralAuthority,
         mintAccount: mintAccount,
         systemProgram: anchor.web3.SystemProgram.programId,
+        eventAuthority,
+        program: program.programId,
         auctionHouseAddress: auctionProgram,
         feeAccount: feeAta.address,
         mintCreator, 
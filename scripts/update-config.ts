@@ -51,22 +51,28 @@ import { getOrCreateAssociatedTokenAccount } from "@solana/spl-token";
     [Buffer.from("central_authority")],
     program.programId
   )[0];
+  const eventAuthority = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("__event_authority")],
+    program.programId
+  )[0];
 
   // input mint account
   const mintAccount = new anchor.web3.PublicKey(
     process.env.MINT_ACCOUNT_ADDRESS
   );
 
+  // raw units of the configured mint
   let baseCost = null;
 
-  if (parseFloat(process.env.NEW_PRICE)) {
-    baseCost = parseFloat(process.env.NEW_PRICE);
+  if (process.env.NEW_PRICE) {
+    baseCost = new anchor.BN(process.env.NEW_PRICE);
   }
 
-  let adminQuota = null;
+  // basis points, e.g. 3000 for 30%
+  let adminQuotaBps = null;
 
-  if (parseFloat(process.env.ADMIN_QUOTA)) {
-    adminQuota = parseFloat(process.env.ADMIN_QUOTA);
+  if (process.env.ADMIN_QUOTA_BPS) {
+    adminQuotaBps = parseInt(process.env.ADMIN_QUOTA_BPS);
   }
 
   let newMerkleTree = null;
@@ -112,22 +118,30 @@ import { getOrCreateAssociatedTokenAccount } from "@solana/spl-token";
 
   let ix = await program.methods
     .updateConfig({
-      baseCost:null,
-      adminQuota:null,
-      multiplier: null,
-      feeAccount: null,
       auctionHouseAddress: null,
+      disputeWindowSeconds: null,
+      arbiter: null,
+      authorityTimelockSeconds: null,
       mintAddress: null,
-      centralizedAccount: null, 
-      mintCreator, 
-      verificationCreator, 
       royaltiesReceiver: null,
+      mintCreator,
+      verificationCreator,
+      pricingAdmin: null,
+      feeAdmin: null,
+      mintingSigner: null,
+      pauseGuardian: null,
+      slotMinutes: null,
+      maxAdvanceSeconds: null,
+      minLeadSeconds: null,
+      runningSlotBookable: null,
     })
     .accountsStrict({
       centralAuthority,
       centralizedAccount: centralizedAccount.publicKey,
       mintAccount: mintAccount,
       systemProgram: anchor.web3.SystemProgram.programId,
+      eventAuthority,
+      program: program.programId,
     })
     .instruction();
 
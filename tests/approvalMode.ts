@@ -0,0 +1,222 @@
+import { associatedAddress } from "@coral-xyz/anchor/dist/cjs/utils/token";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+} from "@metaplex-foundation/mpl-bubblegum";
+import { SYSTEM_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/native/system";
+import assert from "assert";
+import {
+  LEAF_MISMATCH,
+  RentalEnv,
+  expectError,
+  findLandPda,
+  landAssetId,
+  randomSlotStart,
+  tokenBalance,
+} from "./utils/rentalEnv";
+
+const HOUR = 60 * 60;
+
+describe("approval mode", () => {
+  const env = new RentalEnv();
+  let restore: Record<string, unknown>;
+
+  before(async () => {
+    restore = await env.useShortRentals();
+    await setBookingMode(true);
+  });
+
+  after(async () => {
+    await setBookingMode(false);
+    await env.updateConfig(restore);
+  });
+
+  const setBookingMode = async (
+    approvalRequired: boolean,
+    landOwner: Keypair = env.landOwner
+  ) => {
+    const { leaf, accounts } = await env.landSettingAccounts(landOwner);
+    const ix = await env.program.methods
+      .setLandBookingMode(landAssetId, approvalRequired, leaf.leafData)
+      .accountsStrict({
+        ...accounts,
+        landBookingMode: findLandPda(
+          env.program.programId,
+          "land_booking_mode",
+          landAssetId
+        ),
+      })
+      .remainingAccounts(leaf.proof)
+      .instruction();
+
+    return env.send([ix], [landOwner]);
+  };
+
+  const approveRental = async (
+    rentEscrow: PublicKey,
+    landOwner: Keypair = env.landOwner
+  ) => {
+    const escrow = await env.escrow(rentEscrow);
+    const { leaf, accounts } = await env.landSettingAccounts(landOwner);
+    const ix = await env.program.methods
+      .approveRental(await env.rentalMetadata(), leaf.leafData)
+      .accountsStrict({
+        centralAuthority: env.centralAuthority,
+        mintingSigner: env.centralizedAccount.publicKey,
+        landOwner: landOwner.publicKey,
+        landDelegate: accounts.landDelegate,
+        landMerkleTree: accounts.landMerkleTree,
+        renter: escrow.renter,
+        rentEscrow,
+        rentalMerkleTree: env.rentalMerkleTree.publicKey,
+        treeConfig: env.treeConfig,
+        ...env.collectionAccounts(),
+        systemProgram: SYSTEM_PROGRAM_ID,
+        eventAuthority: env.eventAuthority,
+        program: env.program.programId,
+      })
+      .remainingAccounts(leaf.proof)
+      .instruction();
+
+    return env.send([ix], [landOwner]);
+  };
+
+  // Accounts shared by `reject_rental` and `expire_rental_request` to refund a request
+  const refundAccounts = async (rentEscrow: PublicKey) => {
+    const escrow = await env.escrow(rentEscrow);
+
+    return {
+      centralAuthority: env.centralAuthority,
+      mint: env.mintAccount,
+      renter: escrow.renter,
+      renterAta: escrow.renterAta,
+      feeAccount: env.feeAccount,
+      rentEscrow,
+      rentEscrowAta: associatedAddress({
+        mint: env.mintAccount,
+        owner: rentEscrow,
+      }),
+      ...env.dayBookings(
+        escrow.landAssetId,
+        escrow.startTime.toNumber(),
+        escrow.endTime.toNumber()
+      ),
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SYSTEM_PROGRAM_ID,
+      eventAuthority: env.eventAuthority,
+      program: env.program.programId,
+    };
+  };
+
+  const rejectRental = async (
+    rentEscrow: PublicKey,
+    landOwner: Keypair = env.landOwner
+  ) => {
+    const { leaf, accounts } = await env.landSettingAccounts(landOwner);
+    const ix = await env.program.methods
+      .rejectRental(leaf.leafData)
+      .accountsStrict({
+        ...(await refundAccounts(rentEscrow)),
+        landOwner: landOwner.publicKey,
+        landDelegate: accounts.landDelegate,
+        landMerkleTree: accounts.landMerkleTree,
+        compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+      })
+      .remainingAccounts(leaf.proof)
+      .instruction();
+
+    return env.send([ix], [landOwner]);
+  };
+
+  const expireRentalRequest = async (rentEscrow: PublicKey) => {
+    const ix = await env.program.methods
+      .expireRentalRequest()
+      .accountsStrict(await refundAccounts(rentEscrow))
+      .instruction();
+
+    return env.send([ix], []);
+  };
+
+  const futureStart = async () =>
+    randomSlotStart(await env.slotSeconds(), 72 * HOUR, 96 * HOUR);
+
+  // Answers the request with `answer` and checks the renter got its whole payment back
+  const checkFullRefund = async (
+    rentEscrow: PublicKey,
+    answer: () => Promise<unknown>
+  ) => {
+    const { expectedCost, renterAta } = await env.escrow(rentEscrow);
+    const renterBefore = await tokenBalance(env.connection, renterAta);
+
+    await answer();
+
+    const renterAfter = await tokenBalance(env.connection, renterAta);
+    assert.equal(
+      renterAfter.sub(renterBefore).toString(),
+      expectedCost.toString()
+    );
+    assert.equal(await env.connection.getAccountInfo(rentEscrow), null);
+  };
+
+  it("should only let the land owner set the booking mode", async () => {
+    await expectError(setBookingMode(false, env.caller), LEAF_MISMATCH);
+  });
+
+  it("should mint the rental once the land owner approves it", async () => {
+    const rentEscrow = await env.mintRental(await futureStart());
+
+    let escrow = await env.escrow(rentEscrow);
+    assert.ok(!escrow.approvalDeadline.isZero());
+    assert.ok(escrow.rentalAssetId.equals(PublicKey.default));
+
+    await expectError(approveRental(rentEscrow, env.caller), LEAF_MISMATCH);
+
+    await approveRental(rentEscrow);
+
+    escrow = await env.escrow(rentEscrow);
+    assert.ok(escrow.approvalDeadline.isZero());
+    assert.ok(escrow.rentalMerkleTree.equals(env.rentalMerkleTree.publicKey));
+    const rental = await env.leaf(escrow.rentalAssetId);
+    assert.ok(rental.owner.equals(env.caller.publicKey));
+
+    await expectError(
+      approveRental(rentEscrow),
+      "Rental is not waiting for approval"
+    );
+
+    await env.send([await env.cancelRentalIx(rentEscrow)], [env.caller]);
+  });
+
+  it("should refund a rejected request and free its slot", async () => {
+    const startTime = await futureStart();
+    const rentEscrow = await env.mintRental(startTime);
+
+    await expectError(rejectRental(rentEscrow, env.caller), LEAF_MISMATCH);
+    await checkFullRefund(rentEscrow, () => rejectRental(rentEscrow));
+
+    const rebooked = await env.mintRental(startTime);
+    await rejectRental(rebooked);
+  });
+
+  it("should let anyone expire a request past its deadline", async () => {
+    const futureRequest = await env.mintRental(await futureStart());
+    await expectError(
+      expireRentalRequest(futureRequest),
+      "Rental request can still be answered by the land owner"
+    );
+    await rejectRental(futureRequest);
+
+    // A request for the running slot can't be answered anymore
+    const { rentEscrow } = await env.mintRunningRental();
+    await expectError(
+      approveRental(rentEscrow),
+      "Rental request can no longer be approved"
+    );
+    await checkFullRefund(rentEscrow, () => expireRentalRequest(rentEscrow));
+  });
+});
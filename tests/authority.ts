@@ -0,0 +1,123 @@
+import { BN } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import assert from "assert";
+import { sleep } from "../helper";
+import { RentalEnv, expectError } from "./utils/rentalEnv";
+
+const TIMELOCK_SECONDS = 10;
+
+describe("authority transfer", () => {
+  const env = new RentalEnv();
+  const newAuthority = Keypair.generate();
+  let authorityTimelockSeconds: BN;
+
+  before(async () => {
+    ({ authorityTimelockSeconds } = await env.config());
+    await env.updateConfig({
+      authorityTimelockSeconds: new BN(TIMELOCK_SECONDS),
+    });
+  });
+
+  after(async () => {
+    await env.updateConfig({ authorityTimelockSeconds });
+  });
+
+  const proposeAuthority = async (authority: Keypair, proposed: PublicKey) => {
+    const ix = await env.program.methods
+      .proposeAuthority(proposed)
+      .accountsStrict({
+        centralAuthority: env.centralAuthority,
+        centralizedAccount: authority.publicKey,
+        eventAuthority: env.eventAuthority,
+        program: env.program.programId,
+      })
+      .instruction();
+
+    return env.send([ix], [authority]);
+  };
+
+  const acceptAuthority = async (pendingAuthority: Keypair) => {
+    const ix = await env.program.methods
+      .acceptAuthority()
+      .accountsStrict({
+        centralAuthority: env.centralAuthority,
+        pendingAuthority: pendingAuthority.publicKey,
+        eventAuthority: env.eventAuthority,
+        program: env.program.programId,
+      })
+      .instruction();
+
+    return env.send([ix], [pendingAuthority]);
+  };
+
+  const cancelAuthorityProposal = async (authority: Keypair) => {
+    const ix = await env.program.methods
+      .cancelAuthorityProposal()
+      .accountsStrict({
+        centralAuthority: env.centralAuthority,
+        centralizedAccount: authority.publicKey,
+        eventAuthority: env.eventAuthority,
+        program: env.program.programId,
+      })
+      .instruction();
+
+    return env.send([ix], [authority]);
+  };
+
+  it("should only let the current authority propose a successor", async () => {
+    await expectError(
+      proposeAuthority(env.caller, env.caller.publicKey),
+      "Invalid authority provided!"
+    );
+  });
+
+  it("should not hand over before the timelock or once cancelled", async () => {
+    await proposeAuthority(env.centralizedAccount, newAuthority.publicKey);
+
+    const config = await env.config();
+    assert.ok(config.pendingAuthority.equals(newAuthority.publicKey));
+
+    await expectError(
+      acceptAuthority(env.caller),
+      "Invalid authority provided!"
+    );
+    await expectError(
+      acceptAuthority(newAuthority),
+      "Authority transfer timelock has not elapsed yet"
+    );
+
+    await cancelAuthorityProposal(env.centralizedAccount);
+    await sleep(TIMELOCK_SECONDS * 1000);
+
+    await expectError(
+      acceptAuthority(newAuthority),
+      "No authority transfer is pending"
+    );
+  });
+
+  it("should hand over to the accepted authority after the timelock", async () => {
+    await proposeAuthority(env.centralizedAccount, newAuthority.publicKey);
+    await sleep((TIMELOCK_SECONDS + 5) * 1000);
+    await acceptAuthority(newAuthority);
+
+    let config = await env.config();
+    assert.ok(config.centralizedAccount.equals(newAuthority.publicKey));
+    assert.ok(config.pendingAuthority.equals(PublicKey.default));
+
+    // The previous authority lost its rights
+    await expectError(
+      proposeAuthority(env.centralizedAccount, env.caller.publicKey),
+      "Invalid authority provided!"
+    );
+
+    // Hands the authority back for the other tests
+    await proposeAuthority(newAuthority, env.centralizedAccount.publicKey);
+    await sleep((TIMELOCK_SECONDS + 5) * 1000);
+    await acceptAuthority(env.centralizedAccount);
+
+    config = await env.config();
+    assert.ok(
+      config.centralizedAccount.equals(env.centralizedAccount.publicKey)
+    );
+  });
+});
@@ -0,0 +1,108 @@
+import { associatedAddress } from "@coral-xyz/anchor/dist/cjs/utils/token";
+import assert from "assert";
+import {
+  BPS_DENOMINATOR,
+  RentalEnv,
+  expectError,
+  randomSlotStart,
+  tokenBalance,
+} from "./utils/rentalEnv";
+
+const HOUR = 60 * 60;
+
+describe("cancel_rental", () => {
+  const env = new RentalEnv();
+
+  // mirrors `Data::cancellation_refund_bps`
+  const refundBps = async (noticeSeconds: number) => {
+    const covered = (await env.config()).cancellationTiers
+      .filter((tier) => noticeSeconds >= tier.minNoticeSeconds.toNumber())
+      .sort((a, b) => b.minNoticeSeconds.cmp(a.minNoticeSeconds));
+
+    return covered.length > 0 ? covered[0].refundBps : 0;
+  };
+
+  const cancelAndCheckRefund = async (startTime: number) => {
+    const rentEscrow = await env.mintRental(startTime);
+    const { expectedCost, feeQuota, landAssetId } = await env.escrow(
+      rentEscrow
+    );
+    const { owner } = await env.leaf(landAssetId);
+    const renterAta = env.ata(env.caller.publicKey);
+    const feeAccountAta = await env.createAta(env.feeAccount);
+    const receiverAta = await env.createAta(await env.paymentReceiver(owner));
+
+    const renterBefore = await tokenBalance(env.connection, renterAta);
+    const feeBefore = await tokenBalance(env.connection, feeAccountAta);
+    const receiverBefore = await tokenBalance(env.connection, receiverAta);
+
+    const ix = await env.cancelRentalIx(rentEscrow);
+    const bps = await refundBps(startTime - Math.floor(Date.now() / 1000));
+    await env.send([ix], [env.caller]);
+
+    // The retained part is split like a settlement, pro rata of the fee
+    const refund = expectedCost.muln(bps).divn(BPS_DENOMINATOR);
+    const retained = expectedCost.sub(refund);
+    const fee = feeQuota.mul(retained).div(expectedCost);
+    const renterAfter = await tokenBalance(env.connection, renterAta);
+    const feeAfter = await tokenBalance(env.connection, feeAccountAta);
+    const receiverAfter = await tokenBalance(env.connection, receiverAta);
+
+    assert.equal(renterAfter.sub(renterBefore).toString(), refund.toString());
+    assert.equal(feeAfter.sub(feeBefore).toString(), fee.toString());
+    assert.equal(
+      receiverAfter.sub(receiverBefore).toString(),
+      retained.sub(fee).toString()
+    );
+    assert.equal(await env.connection.getAccountInfo(rentEscrow), null);
+    assert.equal(
+      await env.connection.getAccountInfo(
+        associatedAddress({ mint: env.mintAccount, owner: rentEscrow })
+      ),
+      null
+    );
+
+    return bps;
+  };
+
+  it("should refund the full cost when cancelled more than 48h ahead", async () => {
+    const startTime = randomSlotStart(
+      await env.slotSeconds(),
+      72 * HOUR,
+      96 * HOUR
+    );
+
+    assert.equal(await cancelAndCheckRefund(startTime), BPS_DENOMINATOR);
+  });
+
+  it("should refund half the cost when cancelled less than 48h ahead", async () => {
+    const startTime = randomSlotStart(
+      await env.slotSeconds(),
+      2 * HOUR,
+      24 * HOUR
+    );
+
+    assert.equal(await cancelAndCheckRefund(startTime), BPS_DENOMINATOR / 2);
+  });
+
+  it("should release the slot of a cancelled rental", async () => {
+    const startTime = randomSlotStart(
+      await env.slotSeconds(),
+      72 * HOUR,
+      96 * HOUR
+    );
+
+    await cancelAndCheckRefund(startTime);
+    const rentEscrow = await env.mintRental(startTime);
+    await env.send([await env.cancelRentalIx(rentEscrow)], [env.caller]);
+  });
+
+  it("should fail to cancel a rental that already started", async () => {
+    const { rentEscrow } = await env.mintRunningRental();
+
+    await expectError(
+      env.send([await env.cancelRentalIx(rentEscrow)], [env.caller]),
+      "Rental has already started"
+    );
+  });
+});
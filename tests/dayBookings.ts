@@ -0,0 +1,121 @@
+import assert from "assert";
+import { PublicKey } from "@solana/web3.js";
+import {
+  RentalEnv,
+  expectError,
+  findDayBookingsPda,
+  landAssetId,
+} from "./utils/rentalEnv";
+
+const DAY = 24 * 60 * 60;
+
+describe("day bookings", () => {
+  const env = new RentalEnv();
+
+  // Midnight a few days ahead, random so reruns don't collide with earlier bookings
+  const futureMidnight = () =>
+    (Math.floor(Date.now() / 1000 / DAY) + 3 + Math.floor(Math.random() * 4)) *
+    DAY;
+
+  const cancel = async (rentEscrow: PublicKey) =>
+    env.send([await env.cancelRentalIx(rentEscrow)], [env.caller]);
+
+  it("should book a range of slots in one rental", async () => {
+    const slotSeconds = await env.slotSeconds();
+    const startTime = futureMidnight() + 10 * slotSeconds;
+
+    const rentEscrow = await env.mintRental(startTime, 3);
+    const escrow = await env.escrow(rentEscrow);
+
+    assert.equal(escrow.startTime.toNumber(), startTime);
+    assert.equal(escrow.endTime.toNumber(), startTime + 3 * slotSeconds);
+
+    const bookings = await env.program.account.dayBookings.fetch(
+      findDayBookingsPda(env.program.programId, landAssetId, startTime)
+    );
+    assert.ok(bookings.landAssetId.equals(landAssetId));
+    assert.equal(bookings.day.toNumber(), Math.floor(startTime / DAY));
+
+    await cancel(rentEscrow);
+  });
+
+  it("should fail to book a slot taken by another rental", async () => {
+    const slotSeconds = await env.slotSeconds();
+    const startTime = futureMidnight() + 20 * slotSeconds;
+
+    const rentEscrow = await env.mintRental(startTime, 2);
+
+    await expectError(
+      env.mintRental(startTime + slotSeconds),
+      "One of the requested slots is already booked"
+    );
+    await expectError(
+      env.mintRental(startTime - slotSeconds, 2),
+      "One of the requested slots is already booked"
+    );
+
+    await cancel(rentEscrow);
+  });
+
+  it("should book a rental crossing midnight on both days", async () => {
+    const slotSeconds = await env.slotSeconds();
+    const midnight = futureMidnight();
+    const startTime = midnight - slotSeconds;
+
+    await expectError(
+      env.send(
+        [
+          await env.mintRentalIx(startTime, 2, env.caller, landAssetId, {
+            nextDayBookings: null,
+          }),
+        ],
+        [env.caller]
+      ),
+      "Rental continues into the next day, its day bookings account is required"
+    );
+
+    const rentEscrow = await env.mintRental(startTime, 2);
+
+    const nextDayBookings = await env.program.account.dayBookings.fetch(
+      findDayBookingsPda(env.program.programId, landAssetId, midnight)
+    );
+    assert.equal(nextDayBookings.day.toNumber(), midnight / DAY);
+
+    // The first slot of the next day is taken
+    await expectError(
+      env.mintRental(midnight),
+      "One of the requested slots is already booked"
+    );
+
+    await cancel(rentEscrow);
+  });
+
+  it("should fail to close the bookings of a day still in use", async () => {
+    const slotSeconds = await env.slotSeconds();
+    const startTime = futureMidnight() + 30 * slotSeconds;
+
+    const rentEscrow = await env.mintRental(startTime);
+
+    const ix = await env.program.methods
+      .closeDayBookings()
+      .accountsStrict({
+        centralAuthority: env.centralAuthority,
+        dayBookings: findDayBookingsPda(
+          env.program.programId,
+          landAssetId,
+          startTime
+        ),
+        mintingSigner: env.centralizedAccount.publicKey,
+        eventAuthority: env.eventAuthority,
+        program: env.program.programId,
+      })
+      .instruction();
+
+    await expectError(
+      env.send([ix], []),
+      "Bookings of a day can only be closed once the following day has ended"
+    );
+
+    await cancel(rentEscrow);
+  });
+});
@@ -0,0 +1,182 @@
+import { BN } from "@coral-xyz/anchor";
+import { associatedAddress } from "@coral-xyz/anchor/dist/cjs/utils/token";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+} from "@metaplex-foundation/mpl-bubblegum";
+import { SYSTEM_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/native/system";
+import assert from "assert";
+import {
+  BPS_DENOMINATOR,
+  RentalEnv,
+  expectError,
+  randomSlotStart,
+  tokenBalance,
+} from "./utils/rentalEnv";
+
+describe("disputes", () => {
+  const env = new RentalEnv();
+  let restore: Record<string, unknown>;
+  let rentEscrow: PublicKey;
+
+  before(async () => {
+    restore = await env.useShortRentals();
+    await env.updateConfig({ disputeWindowSeconds: new BN(600) });
+    ({ rentEscrow } = await env.mintRunningRental());
+  });
+
+  after(async () => {
+    await env.updateConfig(restore);
+  });
+
+  const openDisputeIx = async (
+    rentEscrow: PublicKey,
+    renter: Keypair = env.caller
+  ) =>
+    env.program.methods
+      .openDispute()
+      .accountsStrict({
+        centralAuthority: env.centralAuthority,
+        renter: renter.publicKey,
+        rentEscrow,
+        eventAuthority: env.eventAuthority,
+        program: env.program.programId,
+      })
+      .instruction();
+
+  const resolveDisputeIx = async (
+    rentEscrow: PublicKey,
+    renterRefundBps: number,
+    arbiter: Keypair = env.centralizedAccount
+  ) => {
+    const escrow = await env.escrow(rentEscrow);
+    const { leafData, proof, owner, delegate } = await env.leaf(
+      escrow.landAssetId
+    );
+    const paymentReceiver = await env.paymentReceiver(owner);
+
+    return env.program.methods
+      .resolveDispute(leafData, renterRefundBps)
+      .accountsStrict({
+        centralAuthority: env.centralAuthority,
+        arbiter: arbiter.publicKey,
+        mint: env.mintAccount,
+        renter: escrow.renter,
+        renterAta: escrow.renterAta,
+        feeAccount: env.feeAccount,
+        feeAccountAta: await env.createAta(env.feeAccount),
+        landOwner: owner,
+        landDelegate: delegate,
+        paymentReceiver,
+        paymentReceiverAta: await env.createAta(paymentReceiver),
+        rentEscrow,
+        rentEscrowAta: associatedAddress({
+          mint: env.mintAccount,
+          owner: rentEscrow,
+        }),
+        merkleTree: env.landMerkleTree.publicKey,
+        compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SYSTEM_PROGRAM_ID,
+        eventAuthority: env.eventAuthority,
+        program: env.program.programId,
+      })
+      .remainingAccounts(proof)
+      .instruction();
+  };
+
+  it("should fail to dispute a rental that hasn't started", async () => {
+    const startTime = randomSlotStart(await env.slotSeconds(), 3600, 7200);
+    const futureEscrow = await env.mintRental(startTime);
+
+    await expectError(
+      env.send([await openDisputeIx(futureEscrow)], [env.caller]),
+      "Disputes can only be opened from the rental start until the dispute window ends"
+    );
+    await expectError(
+      env.send([await resolveDisputeIx(futureEscrow, 0)], []),
+      "Rental is not disputed"
+    );
+
+    await env.send([await env.cancelRentalIx(futureEscrow)], [env.caller]);
+  });
+
+  it("should fail to dispute someone else's rental", async () => {
+    await expectError(
+      env.send([await openDisputeIx(rentEscrow, env.centralizedAccount)], []),
+      "A has one constraint was violated"
+    );
+  });
+
+  it("should open a dispute and hold the settlement", async () => {
+    await env.send([await openDisputeIx(rentEscrow)], [env.caller]);
+    assert.ok((await env.escrow(rentEscrow)).disputed);
+
+    await expectError(
+      env.send([await openDisputeIx(rentEscrow)], [env.caller]),
+      "Rental is already disputed"
+    );
+    await expectError(
+      env.send([await env.settleBatchIx([rentEscrow])], []),
+      "Rental is already disputed"
+    );
+  });
+
+  it("should only let the arbiter resolve a dispute", async () => {
+    await expectError(
+      env.send(
+        [await resolveDisputeIx(rentEscrow, 0, env.caller)],
+        [env.caller]
+      ),
+      "Invalid authority provided!"
+    );
+    await expectError(
+      env.send([await resolveDisputeIx(rentEscrow, BPS_DENOMINATOR + 1)], []),
+      "Basis points value can't exceed 10000"
+    );
+  });
+
+  it("should split the escrow as decided by the arbiter", async () => {
+    const escrow = await env.escrow(rentEscrow);
+    const { owner } = await env.leaf(escrow.landAssetId);
+    const paymentReceiverAta = await env.createAta(
+      await env.paymentReceiver(owner)
+    );
+    const feeAccountAta = await env.createAta(env.feeAccount);
+
+    const renterBefore = await tokenBalance(env.connection, escrow.renterAta);
+    const feeBefore = await tokenBalance(env.connection, feeAccountAta);
+    const ownerBefore = await tokenBalance(env.connection, paymentReceiverAta);
+
+    await env.send([await resolveDisputeIx(rentEscrow, 7_000)], []);
+
+    const refund = escrow.expectedCost.muln(7_000).divn(BPS_DENOMINATOR);
+    const fee = escrow.feeQuota.muln(3_000).divn(BPS_DENOMINATOR);
+    const ownerPayment = escrow.expectedCost.sub(refund).sub(fee);
+
+    assert.equal(
+      (await tokenBalance(env.connection, escrow.renterAta))
+        .sub(renterBefore)
+        .toString(),
+      refund.toString()
+    );
+    assert.equal(
+      (await tokenBalance(env.connection, feeAccountAta))
+        .sub(feeBefore)
+        .toString(),
+      fee.toString()
+    );
+    assert.equal(
+      (await tokenBalance(env.connection, paymentReceiverAta))
+        .sub(ownerBefore)
+        .toString(),
+      ownerPayment.toString()
+    );
+    assert.equal(await env.connection.getAccountInfo(rentEscrow), null);
+  });
+});
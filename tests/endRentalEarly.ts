@@ -0,0 +1,218 @@
+import { associatedAddress } from "@coral-xyz/anchor/dist/cjs/utils/token";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { SYSTEM_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/native/system";
+import assert from "assert";
+import {
+  RentalEnv,
+  expectError,
+  findLandPda,
+  randomSlotStart,
+  tokenBalance,
+} from "./utils/rentalEnv";
+
+const HOUR = 60 * 60;
+
+describe("end_rental_early", () => {
+  const env = new RentalEnv();
+  let restore: Record<string, unknown>;
+
+  before(async () => {
+    restore = await env.useShortRentals();
+  });
+
+  after(async () => {
+    await env.updateConfig(restore);
+  });
+
+  const endRentalEarlyIx = async (
+    rentEscrow: PublicKey,
+    renter: Keypair = env.caller
+  ) => {
+    const { escrow, rental, accounts } = await env.rentalAccounts(rentEscrow);
+    const land = await env.leaf(escrow.landAssetId);
+    const paymentReceiver = await env.paymentReceiver(land.owner);
+
+    return env.program.methods
+      .endRentalEarly(land.leafData, rental.leafData, land.proof.length)
+      .accountsStrict({
+        centralAuthority: env.centralAuthority,
+        mint: env.mintAccount,
+        renter: renter.publicKey,
+        renterAta: await env.createAta(renter.publicKey),
+        feeAccount: env.feeAccount,
+        feeAccountAta: await env.createAta(env.feeAccount),
+        landOwner: land.owner,
+        landDelegate: land.delegate,
+        paymentReceiver,
+        paymentReceiverAta: await env.createAta(paymentReceiver),
+        rentEscrow,
+        rentEscrowAta: associatedAddress({
+          mint: env.mintAccount,
+          owner: rentEscrow,
+        }),
+        ...env.dayBookings(
+          escrow.landAssetId,
+          escrow.startTime.toNumber(),
+          escrow.endTime.toNumber()
+        ),
+        landStatus: findLandPda(
+          env.program.programId,
+          "land_status",
+          escrow.landAssetId
+        ),
+        landMerkleTree: env.landMerkleTree.publicKey,
+        ...accounts,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SYSTEM_PROGRAM_ID,
+        eventAuthority: env.eventAuthority,
+        program: env.program.programId,
+      })
+      .remainingAccounts([...land.proof, ...rental.proof])
+      .instruction();
+  };
+
+  const futureStart = async () =>
+    randomSlotStart(await env.slotSeconds(), 72 * HOUR, 96 * HOUR);
+
+  it("should only let the renter end the rental", async () => {
+    const rentEscrow = await env.mintRental(await futureStart());
+
+    await expectError(
+      env.send(
+        [await endRentalEarlyIx(rentEscrow, env.centralizedAccount)],
+        []
+      ),
+      "A has one constraint was violated"
+    );
+
+    await env.send([await env.cancelRentalIx(rentEscrow)], [env.caller]);
+  });
+
+  it("should fail to end a rental that has not started", async () => {
+    const rentEscrow = await env.mintRental(await futureStart());
+
+    await expectError(
+      env.send([await endRentalEarlyIx(rentEscrow)], [env.caller]),
+      "Rental has not started yet"
+    );
+
+    await env.send([await env.cancelRentalIx(rentEscrow)], [env.caller]);
+  });
+
+  it("should fail to end a rental that ended", async () => {
+    const rentEscrow = await env.mintEndedRental();
+
+    await expectError(
+      env.send([await endRentalEarlyIx(rentEscrow)], [env.caller]),
+      "Rental has already ended"
+    );
+
+    await env.send([await env.settleBatchIx([rentEscrow])], []);
+  });
+
+  // Ends a rental of `slotCount` slots booked from the running slot, calling
+  // `beforeEnd` in between, and checks the used slots are paid pro rata of
+  // the locked cost and fee
+  const checkEarlyEnd = async (
+    slotCount: number,
+    beforeEnd: () => Promise<unknown> = async () => {}
+  ) => {
+    const slotSeconds = await env.slotSeconds();
+    const startTime =
+      Math.floor(Date.now() / 1000 / slotSeconds) * slotSeconds;
+    const rentEscrow = await env.mintRental(startTime, slotCount);
+    const { landAssetId, expectedCost, feeQuota, renterAta } =
+      await env.escrow(rentEscrow);
+
+    await beforeEnd();
+
+    const { owner } = await env.leaf(landAssetId);
+    const paymentReceiverAta = env.ata(await env.paymentReceiver(owner));
+    const feeAccountAta = await env.createAta(env.feeAccount);
+    const renterBefore = await tokenBalance(env.connection, renterAta);
+    const receiverBefore = await tokenBalance(
+      env.connection,
+      paymentReceiverAta
+    );
+    const feeBefore = await tokenBalance(env.connection, feeAccountAta);
+
+    const signature = await env.send(
+      [await endRentalEarlyIx(rentEscrow)],
+      [env.caller]
+    );
+
+    // The slot in progress when the transaction landed counts as used
+    const { blockTime } = await env.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const usedSlots = Math.min(
+      Math.floor((blockTime - startTime) / slotSeconds) + 1,
+      slotCount
+    );
+    const usedCost = expectedCost.muln(usedSlots).divn(slotCount);
+    const fee = feeQuota.muln(usedSlots).divn(slotCount);
+
+    const renterAfter = await tokenBalance(env.connection, renterAta);
+    const receiverAfter = await tokenBalance(
+      env.connection,
+      paymentReceiverAta
+    );
+    const feeAfter = await tokenBalance(env.connection, feeAccountAta);
+    assert.equal(
+      renterAfter.sub(renterBefore).toString(),
+      expectedCost.sub(usedCost).toString()
+    );
+    assert.equal(
+      receiverAfter.sub(receiverBefore).toString(),
+      usedCost.sub(fee).toString()
+    );
+    assert.equal(feeAfter.sub(feeBefore).toString(), fee.toString());
+    assert.equal(await env.connection.getAccountInfo(rentEscrow), null);
+
+    return { startTime, slotSeconds, usedSlots };
+  };
+
+  it("should pay the used slots and refund the rest", async () => {
+    const slotCount = 4;
+    const { startTime, slotSeconds } = await checkEarlyEnd(slotCount);
+
+    // The last slot is free again
+    const rebooked = await env.mintRental(
+      startTime + (slotCount - 1) * slotSeconds
+    );
+    await env.send(
+      [await env.voidRentalIx(rebooked, 1, env.centralizedAccount)],
+      []
+    );
+  });
+
+  it("should split the locked cost when the price changed since booking", async () => {
+    const price = await env.slotBasePrice();
+    const { adminQuotaBps } = await env.config();
+
+    try {
+      await checkEarlyEnd(4, async () => {
+        await env.setLandPricing(price.muln(3));
+        await env.updateFees({
+          adminQuotaBps: (adminQuotaBps + 1_000) % 10_000,
+        });
+      });
+    } finally {
+      await env.setLandPricing(price);
+      await env.updateFees({ adminQuotaBps });
+    }
+  });
+
+  it("should end a rental during its last slot", async () => {
+    // No slot is left to free once the single booked slot started
+    const { usedSlots } = await checkEarlyEnd(1);
+
+    assert.equal(usedSlots, 1);
+  });
+});
@@ -0,0 +1,173 @@
+import { associatedAddress } from "@coral-xyz/anchor/dist/cjs/utils/token";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { PublicKey } from "@solana/web3.js";
+import { SYSTEM_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/native/system";
+import assert from "assert";
+import {
+  RentalEnv,
+  expectError,
+  findLandPda,
+  randomSlotStart,
+  rangePrice,
+  tokenBalance,
+} from "./utils/rentalEnv";
+
+const HOUR = 60 * 60;
+
+describe("extend_rental", () => {
+  const env = new RentalEnv();
+
+  const extendRentalIx = async (rentEscrow: PublicKey, slotCount: number) => {
+    const { escrow, accounts } = await env.rentalAccounts(rentEscrow);
+    const land = await env.leaf(escrow.landAssetId);
+    const slotSeconds = escrow.slotSeconds.toNumber();
+    const endTime = escrow.endTime.toNumber();
+    const collection = env.collectionAccounts();
+
+    return env.program.methods
+      .extendRental(slotCount, land.leafData, land.proof.length, null)
+      .accountsStrict({
+        centralAuthority: env.centralAuthority,
+        mint: env.mintAccount,
+        mintingSigner: env.centralizedAccount.publicKey,
+        renter: escrow.renter,
+        renterAta: escrow.renterAta,
+        rentEscrow,
+        rentEscrowAta: associatedAddress({
+          mint: env.mintAccount,
+          owner: rentEscrow,
+        }),
+        // The extension is booked from the current end of the rental
+        ...env.dayBookings(
+          escrow.landAssetId,
+          endTime,
+          endTime + Math.max(slotCount, 1) * slotSeconds
+        ),
+        landPricing: findLandPda(
+          env.program.programId,
+          "land_pricing",
+          escrow.landAssetId
+        ),
+        landAvailability: findLandPda(
+          env.program.programId,
+          "land_availability",
+          escrow.landAssetId
+        ),
+        landStatus: findLandPda(
+          env.program.programId,
+          "land_status",
+          escrow.landAssetId
+        ),
+        landMerkleTree: env.landMerkleTree.publicKey,
+        landOwner: land.owner,
+        landDelegate: land.delegate,
+        ...accounts,
+        collectionMint: collection.collectionMint,
+        collectionMetadata: collection.collectionMetadata,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMetadataProgram: collection.tokenMetadataProgram,
+        systemProgram: SYSTEM_PROGRAM_ID,
+        eventAuthority: env.eventAuthority,
+        program: env.program.programId,
+      })
+      .remainingAccounts(land.proof)
+      .instruction();
+  };
+
+  const extendRental = async (rentEscrow: PublicKey, slotCount: number) =>
+    env.send([await extendRentalIx(rentEscrow, slotCount)], [env.caller]);
+
+  const futureStart = async () =>
+    randomSlotStart(await env.slotSeconds(), 72 * HOUR, 96 * HOUR);
+
+  it("should extend a rental and charge the extra slots", async () => {
+    const startTime = await futureStart();
+    const rentEscrow = await env.mintRental(startTime);
+    const before = await env.escrow(rentEscrow);
+    const slotSeconds = before.slotSeconds.toNumber();
+    const renterBefore = await tokenBalance(env.connection, before.renterAta);
+
+    const price = await env.slotBasePrice();
+    await extendRental(rentEscrow, 2);
+
+    const after = await env.escrow(rentEscrow);
+    const additionalCost = rangePrice(
+      await env.config(),
+      price,
+      before.endTime.toNumber(),
+      slotSeconds,
+      2
+    );
+    assert.equal(
+      after.endTime.toNumber(),
+      before.endTime.toNumber() + 2 * slotSeconds
+    );
+    assert.equal(
+      after.expectedCost.toString(),
+      before.expectedCost.add(additionalCost).toString()
+    );
+    assert.equal(
+      renterBefore
+        .sub(await tokenBalance(env.connection, before.renterAta))
+        .toString(),
+      additionalCost.toString()
+    );
+
+    // The extra slots are booked
+    await expectError(
+      env.mintRental(startTime + 2 * slotSeconds),
+      "One of the requested slots is already booked"
+    );
+
+    await env.send([await env.cancelRentalIx(rentEscrow)], [env.caller]);
+  });
+
+  it("should fail to extend over another booking", async () => {
+    const startTime = await futureStart();
+    const slotSeconds = await env.slotSeconds();
+    const rentEscrow = await env.mintRental(startTime);
+    const nextEscrow = await env.mintRental(startTime + 2 * slotSeconds);
+
+    await expectError(
+      extendRental(rentEscrow, 2),
+      "One of the requested slots is already booked"
+    );
+
+    // Up to the next booking is fine
+    await extendRental(rentEscrow, 1);
+
+    await env.send([await env.cancelRentalIx(rentEscrow)], [env.caller]);
+    await env.send([await env.cancelRentalIx(nextEscrow)], [env.caller]);
+  });
+
+  it("should fail to extend by no slot", async () => {
+    const rentEscrow = await env.mintRental(await futureStart());
+
+    await expectError(
+      extendRental(rentEscrow, 0),
+      "A booking must cover 1 to 255 slots and at most a day"
+    );
+
+    await env.send([await env.cancelRentalIx(rentEscrow)], [env.caller]);
+  });
+
+  it("should fail to extend a rental that ended", async () => {
+    const restore = await env.useShortRentals();
+    try {
+      const rentEscrow = await env.mintEndedRental();
+
+      await expectError(
+        extendRental(rentEscrow, 1),
+        "Rental has already ended"
+      );
+
+      await env.send([await env.settleBatchIx([rentEscrow])], []);
+    } finally {
+      await env.updateConfig(restore);
+    }
+  });
+});
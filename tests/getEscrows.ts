@@ -63,8 +63,10 @@ describe("solana-sky-trade", () => {
   it("should get all rent accounts", async () => {
 
 
-    const escrows = await program.account.rentEscrow.all()
+    const escrows = await program.account.rentalEscrow.all()
+    // still settleable, but no longer created
+    const legacyEscrows = await program.account.rentEscrow.all()
 
-    console.log(JSON.stringify({ escrows }))
+    console.log(JSON.stringify({ escrows, legacyEscrows }))
   });
 });
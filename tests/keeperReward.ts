@@ -0,0 +1,86 @@
+import { associatedAddress } from "@coral-xyz/anchor/dist/cjs/utils/token";
+import { PublicKey } from "@solana/web3.js";
+import assert from "assert";
+import { BPS_DENOMINATOR, RentalEnv, tokenBalance } from "./utils/rentalEnv";
+
+describe("keeper reward", () => {
+  const env = new RentalEnv();
+  const keeper = env.caller;
+  let restoreConfig: Record<string, unknown>;
+  let restoreFees: Record<string, unknown>;
+
+  before(async () => {
+    const { keeperRewardBps, rentToKeeper } = await env.config();
+    restoreFees = { keeperRewardBps, rentToKeeper };
+    restoreConfig = await env.useShortRentals();
+  });
+
+  after(async () => {
+    await env.updateConfig(restoreConfig);
+    await env.updateFees(restoreFees);
+  });
+
+  // Lamports held by the escrow and its ATA, freed when they are closed
+  const escrowRent = async (rentEscrow: PublicKey) =>
+    (await env.connection.getBalance(rentEscrow)) +
+    (await env.connection.getBalance(
+      associatedAddress({ mint: env.mintAccount, owner: rentEscrow })
+    ));
+
+  it("should pay the keeper its share of the fee and the escrow rent", async () => {
+    await env.updateFees({ keeperRewardBps: 2_000, rentToKeeper: true });
+    const rentEscrow = await env.mintEndedRental();
+    const { feeQuota } = await env.escrow(rentEscrow);
+    const rent = await escrowRent(rentEscrow);
+
+    const keeperAta = env.ata(keeper.publicKey);
+    const feeAccountAta = await env.createAta(env.feeAccount);
+    const keeperBefore = await tokenBalance(env.connection, keeperAta);
+    const feeBefore = await tokenBalance(env.connection, feeAccountAta);
+    const lamportsBefore = await env.connection.getBalance(keeper.publicKey);
+
+    await env.send(
+      [await env.settleBatchIx([rentEscrow], keeper, keeperAta)],
+      [keeper]
+    );
+
+    const reward = feeQuota.muln(2_000).divn(BPS_DENOMINATOR);
+    const keeperAfter = await tokenBalance(env.connection, keeperAta);
+    const feeAfter = await tokenBalance(env.connection, feeAccountAta);
+
+    assert.equal(keeperAfter.sub(keeperBefore).toString(), reward.toString());
+    assert.equal(
+      feeAfter.sub(feeBefore).toString(),
+      feeQuota.sub(reward).toString()
+    );
+    assert.equal(
+      (await env.connection.getBalance(keeper.publicKey)) - lamportsBefore,
+      rent
+    );
+  });
+
+  it("should leave the whole fee and rent to the fee account without a keeper ATA", async () => {
+    await env.updateFees({ keeperRewardBps: 2_000, rentToKeeper: false });
+    const rentEscrow = await env.mintEndedRental();
+    const { feeQuota } = await env.escrow(rentEscrow);
+    const rent = await escrowRent(rentEscrow);
+
+    const feeAccountAta = await env.createAta(env.feeAccount);
+    const feeBefore = await tokenBalance(env.connection, feeAccountAta);
+    const feeLamportsBefore = await env.connection.getBalance(env.feeAccount);
+    const lamportsBefore = await env.connection.getBalance(keeper.publicKey);
+
+    await env.send([await env.settleBatchIx([rentEscrow], keeper)], [keeper]);
+
+    const feeAfter = await tokenBalance(env.connection, feeAccountAta);
+    assert.equal(feeAfter.sub(feeBefore).toString(), feeQuota.toString());
+    assert.equal(
+      await env.connection.getBalance(keeper.publicKey),
+      lamportsBefore
+    );
+    assert.equal(
+      (await env.connection.getBalance(env.feeAccount)) - feeLamportsBefore,
+      rent
+    );
+  });
+});
@@ -0,0 +1,101 @@
+import { BN } from "@coral-xyz/anchor";
+import assert from "assert";
+import {
+  LEAF_MISMATCH,
+  RentalEnv,
+  expectError,
+  findLandPda,
+  landAssetId,
+} from "./utils/rentalEnv";
+
+const HOUR = 60 * 60;
+const DAY = 24 * HOUR;
+const ALL_DAY = (1 << 24) - 1;
+
+describe("land availability", () => {
+  const env = new RentalEnv();
+  const alwaysOpen = [
+    ALL_DAY,
+    ALL_DAY,
+    ALL_DAY,
+    ALL_DAY,
+    ALL_DAY,
+    ALL_DAY,
+    ALL_DAY,
+  ];
+
+  after(async () => {
+    await env.setLandAvailability(alwaysOpen, []);
+  });
+
+  // Start of a random hour a few days ahead
+  const futureHour = () =>
+    (Math.floor(Date.now() / 1000 / DAY) + 3 + Math.floor(Math.random() * 4)) *
+      DAY +
+    Math.floor(Math.random() * 24) * HOUR;
+
+  const weekdayOf = (timestamp: number) =>
+    (new Date(timestamp * 1000).getUTCDay() + 6) % 7;
+
+  it("should refuse rentals outside the opening hours", async () => {
+    const closedHour = futureHour();
+    const openHour = closedHour + DAY;
+    const hour = new Date(closedHour * 1000).getUTCHours();
+
+    // Closed during that hour on the weekday of `closedHour` only
+    const weeklyHours = alwaysOpen.slice();
+    weeklyHours[weekdayOf(closedHour)] = ALL_DAY & ~(1 << hour);
+    await env.setLandAvailability(weeklyHours, []);
+
+    const availability = await env.program.account.landAvailability.fetch(
+      findLandPda(env.program.programId, "land_availability", landAssetId)
+    );
+    assert.deepEqual(availability.weeklyHours, weeklyHours);
+    assert.ok(availability.owner.equals(env.landOwner.publicKey));
+
+    await expectError(
+      env.mintRental(closedHour),
+      "Land is not available for the requested slots"
+    );
+
+    const rentEscrow = await env.mintRental(openHour);
+    await env.send([await env.cancelRentalIx(rentEscrow)], [env.caller]);
+  });
+
+  it("should refuse rentals overlapping a blackout", async () => {
+    const blackoutStart = futureHour();
+    const slotSeconds = await env.slotSeconds();
+    await env.setLandAvailability(alwaysOpen, [
+      {
+        startTime: new BN(blackoutStart),
+        endTime: new BN(blackoutStart + HOUR),
+      },
+    ]);
+
+    await expectError(
+      env.mintRental(blackoutStart - slotSeconds, 2),
+      "Land is not available for the requested slots"
+    );
+
+    // The blackout end is excluded
+    const rentEscrow = await env.mintRental(blackoutStart + HOUR);
+    await env.send([await env.cancelRentalIx(rentEscrow)], [env.caller]);
+  });
+
+  it("should fail to set invalid opening hours", async () => {
+    const weeklyHours = alwaysOpen.slice();
+    weeklyHours[0] = 1 << 24;
+
+    await expectError(
+      env.setLandAvailability(weeklyHours, []),
+      "Opening hours must fit in 24 bits and blackouts must be at most 16 non-empty periods"
+    );
+  });
+
+  it("should fail to set the availability of a land owned by someone else", async () => {
+    await expectError(
+      env.setLandAvailability(alwaysOpen, [], env.caller),
+      LEAF_MISMATCH
+    );
+  });
+});
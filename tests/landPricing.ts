@@ -0,0 +1,101 @@
+import { BN } from "@coral-xyz/anchor";
+import assert from "assert";
+import {
+  LEAF_MISMATCH,
+  RentalEnv,
+  expectError,
+  findLandPda,
+  findRentalEscrowPda,
+  landAssetId,
+  randomSlotStart,
+  rangePrice,
+  tokenBalance,
+} from "./utils/rentalEnv";
+
+const HOUR = 60 * 60;
+
+describe("land pricing", () => {
+  const env = new RentalEnv();
+
+  it("should charge rentals the price set by the land owner", async () => {
+    const price = new BN(1_000_000 + Math.floor(Math.random() * 1_000_000));
+    await env.setLandPricing(price);
+
+    const landPricing = await env.program.account.landPricing.fetch(
+      findLandPda(env.program.programId, "land_pricing", landAssetId)
+    );
+    assert.equal(landPricing.price.toString(), price.toString());
+    assert.ok(landPricing.owner.equals(env.landOwner.publicKey));
+
+    const config = await env.config();
+    const slotSeconds = config.slotMinutes * 60;
+    const startTime = randomSlotStart(slotSeconds, 72 * HOUR, 96 * HOUR);
+    const renterAta = env.ata(env.caller.publicKey);
+    const renterBefore = await tokenBalance(env.connection, renterAta);
+
+    const rentEscrow = await env.mintRental(startTime, 2);
+    const escrow = await env.escrow(rentEscrow);
+    const renterAfter = await tokenBalance(env.connection, renterAta);
+
+    const expectedCost = rangePrice(config, price, startTime, slotSeconds, 2);
+    assert.equal(escrow.expectedCost.toString(), expectedCost.toString());
+    assert.equal(
+      renterBefore.sub(renterAfter).toString(),
+      expectedCost.toString()
+    );
+
+    await env.send([await env.cancelRentalIx(rentEscrow)], [env.caller]);
+  });
+
+  it("should fail to book above the maximum cost accepted", async () => {
+    const price = new BN(1_000_000 + Math.floor(Math.random() * 1_000_000));
+    await env.setLandPricing(price);
+
+    const config = await env.config();
+    const slotSeconds = config.slotMinutes * 60;
+    const startTime = randomSlotStart(slotSeconds, 72 * HOUR, 96 * HOUR);
+    const expectedCost = rangePrice(config, price, startTime, slotSeconds, 2);
+    const mintRental = async (maxCost: BN) =>
+      env.send(
+        [
+          await env.mintRentalIx(
+            startTime,
+            2,
+            env.caller,
+            landAssetId,
+            {},
+            maxCost
+          ),
+        ],
+        [env.caller]
+      );
+
+    await expectError(
+      mintRental(expectedCost.subn(1)),
+      "Rental cost is above the maximum accepted by the renter"
+    );
+
+    // The exact cost, admin fee included, is accepted
+    await mintRental(expectedCost);
+    const rentEscrow = findRentalEscrowPda(
+      env.program.programId,
+      landAssetId,
+      startTime
+    )[0];
+    const escrow = await env.escrow(rentEscrow);
+    assert.equal(escrow.expectedCost.toString(), expectedCost.toString());
+
+    await env.send([await env.cancelRentalIx(rentEscrow)], [env.caller]);
+  });
+
+  it("should fail to set a zero price", async () => {
+    await expectError(
+      env.setLandPricing(new BN(0)),
+      "Price must be greater than zero"
+    );
+  });
+
+  it("should fail to set the price of a land owned by someone else", async () => {
+    await expectError(env.setLandPricing(new BN(1), env.caller), LEAF_MISMATCH);
+  });
+});
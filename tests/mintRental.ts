@@ -54,6 +54,7 @@ import { associatedAddress } from "@coral-xyz/anchor/dist/cjs/utils/token";
 import assert from "assert";
 import { ConcurrentMerkleTreeAccount } from "@solana/spl-account-compression";
 import { getAssetDataAndProof } from "./utils/getAssetDataAndProof";
+import { U64_MAX } from "./utils/rentalEnv";
 describe("solana-sky-trade", () => {
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
@@ -68,6 +69,10 @@ describe("solana-sky-trade", () => {
     [Buffer.from("central_authority")],
     program.programId
   )[0];
+  const eventAuthority = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("__event_authority")],
+    program.programId
+  )[0];
   let mintAccEnv = process.env.MINT_ACCOUNT_ADDRESS;
   const mintAccount = new anchor.web3.PublicKey(mintAccEnv);
   const fakemintAccount = new anchor.web3.PublicKey(
@@ -107,6 +112,23 @@ describe("solana-sky-trade", () => {
     merkleTree: publicKey(rentalMerkleTree.publicKey),
   })[0];
 
+  const [landPricing] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("land_pricing"), landAssetId.toBytes()],
+    program.programId
+  );
+  const [landStatus] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("land_status"), landAssetId.toBytes()],
+    program.programId
+  );
+  const [landAvailability] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("land_availability"), landAssetId.toBytes()],
+    program.programId
+  );
+  const [landBookingMode] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("land_booking_mode"), landAssetId.toBytes()],
+    program.programId
+  );
+
   it("should successfully mint an nft", async () => {
     // LAND token data
     const { landAssetLeafData, landAssetProof, landOwner } =
@@ -172,12 +194,14 @@ describe("solana-sky-trade", () => {
 
     umi.use(signerIdentity(callersigner));
 
-    let dateNow = new Date("2024-08-30T15:30:12.738Z").toISOString(); //'2024-08-26T19:25:12.738Z'
-    console.log({ dateNow });
-    
-    let [rent_escrow, bump] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("escrow"), landAssetId.toBytes(), Buffer.from(dateNow)],
-      program.programId
+    // first 30 minutes slot starting a day from now
+    let startTime = Math.ceil((Date.now() / 1000 + 24 * 60 * 60) / 1800) * 1800;
+    console.log({ startTime });
+
+    let [rent_escrow, bump] = findRentalEscrowPda(
+      program.programId,
+      landAssetId,
+      startTime
     );
 
     const rent_escrow_Ata = associatedAddress({
@@ -185,6 +209,12 @@ describe("solana-sky-trade", () => {
       owner: rent_escrow,
     });
 
+    const dayBookings = findDayBookingsPda(
+      program.programId,
+      landAssetId,
+      startTime
+    );
+
     // let leavesDataLength = new anchor.BN(leavesData.length);
     let leavesDataLength = new anchor.BN(1);
 
@@ -192,14 +222,15 @@ describe("solana-sky-trade", () => {
     let ix = await program.methods
       .mintRentalToken(
         landAssetId,
-        dateNow,
+        new anchor.BN(startTime),
         bump,
         Buffer.from(metadataBuffer),
-        landAssetLeafData
+        landAssetLeafData,
+        U64_MAX
       )
       .accountsStrict({
         centralAuthority: centralAuthority,
-        centralizedAccount: centralizedAccount.publicKey,
+        mintingSigner: centralizedAccount.publicKey,
         mint: mintAccount, //alt
         caller: caller.publicKey,
         callerAta: callerAta,
@@ -214,11 +245,19 @@ describe("solana-sky-trade", () => {
         logWrapper: SPL_NOOP_PROGRAM_ID, //alt
         compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID, //alt
         systemProgram: anchor.web3.SystemProgram.programId, //alt
+        eventAuthority,
+        program: program.programId,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID, //alt
         tokenProgram: TOKEN_PROGRAM_ID, //alt
         tokenMetadataProgram: MPL_TOKEN_METADATA_PROGRAM_ID, //alt
         rentEscrow: rent_escrow,
         rentEscrowAta: rent_escrow_Ata,
+        dayBookings,
+        nextDayBookings: null,
+        landPricing,
+        landAvailability,
+        landBookingMode,
+        landStatus,
         landOwner: landOwner,
         landDelegate: landOwner,
       })
@@ -346,12 +385,13 @@ describe("solana-sky-trade", () => {
 
     umi.use(signerIdentity(callersigner));
 
-    let dateNow = new Date("2024-07-25T19:20:12.738Z").toISOString();
-    console.log({ dateNow });
+    let startTime = Math.floor(new Date("2024-07-25T19:20:00.000Z").getTime() / 1000);
+    console.log({ startTime });
 
-    let [rent_escrow, bump] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("escrow"), landAssetId.toBytes(), Buffer.from(dateNow)],
-      program.programId
+    let [rent_escrow, bump] = findRentalEscrowPda(
+      program.programId,
+      landAssetId,
+      startTime
     );
 
     const rent_escrow_Ata = associatedAddress({
@@ -359,19 +399,26 @@ describe("solana-sky-trade", () => {
       owner: rent_escrow,
     });
 
+    const dayBookings = findDayBookingsPda(
+      program.programId,
+      landAssetId,
+      startTime
+    );
+
     let leavesDataLength = new anchor.BN(1);
 
     let ix = await program.methods
       .mintRentalToken(
         landAssetId,
-        dateNow,
+        new anchor.BN(startTime),
         bump,
         Buffer.from(metadataBuffer),
-        landAssetLeafData
+        landAssetLeafData,
+        U64_MAX
       )
       .accountsStrict({
         centralAuthority: centralAuthority,
-        centralizedAccount: centralizedAccount.publicKey,
+        mintingSigner: centralizedAccount.publicKey,
         mint: mintAccount, //alt
         caller: caller.publicKey,
         callerAta: callerAta,
@@ -386,11 +433,19 @@ describe("solana-sky-trade", () => {
         logWrapper: SPL_NOOP_PROGRAM_ID, //alt
         compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID, //alt
         systemProgram: anchor.web3.SystemProgram.programId, //alt
+        eventAuthority,
+        program: program.programId,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID, //alt
         tokenProgram: TOKEN_PROGRAM_ID, //alt
         tokenMetadataProgram: MPL_TOKEN_METADATA_PROGRAM_ID, //alt
         rentEscrow: rent_escrow,
         rentEscrowAta: rent_escrow_Ata,
+        dayBookings,
+        nextDayBookings: null,
+        landPricing,
+        landAvailability,
+        landBookingMode,
+        landStatus,
         landOwner: landOwner,
         landDelegate: landOwner,
       })
@@ -429,7 +484,7 @@ describe("solana-sky-trade", () => {
         console.log("error is", e.message);
 
         let expectedString =
-          "Error Message: Provided minutes in the time should be 00 or 30.";
+          "Error Message: Provided time should be the start of a slot";
         let actualString = e.logs[e.logs.length - 2 - 1] as string;
         let ans = actualString.includes(expectedString);
         console.log({ ans });
@@ -437,7 +492,7 @@ describe("solana-sky-trade", () => {
       });
   });
 
-  it("should fail as iso string is invalid", async () => {
+  it("should fail as start time is not the start of a slot", async () => {
     // LAND token data
     const { landAssetLeafData, landAssetProof, landOwner } =
       await getAssetDataAndProof(landAssetId, umi, provider.connection);
@@ -495,12 +550,13 @@ describe("solana-sky-trade", () => {
 
     umi.use(signerIdentity(callersigner));
 
-    let dateNow = "2024-08-26T19:25:12.738Zjljlh";
-    console.log({ dateNow });
+    let startTime = Math.floor(new Date("2024-08-26T19:30:12.738Z").getTime() / 1000);
+    console.log({ startTime });
 
-    let [rent_escrow, bump] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("escrow"), landAssetId.toBytes(), Buffer.from(dateNow)],
-      program.programId
+    let [rent_escrow, bump] = findRentalEscrowPda(
+      program.programId,
+      landAssetId,
+      startTime
     );
 
     const rent_escrow_Ata = associatedAddress({
@@ -508,19 +564,26 @@ describe("solana-sky-trade", () => {
       owner: rent_escrow,
     });
 
+    const dayBookings = findDayBookingsPda(
+      program.programId,
+      landAssetId,
+      startTime
+    );
+
     let leavesDataLength = new anchor.BN(1);
 
     let ix = await program.methods
       .mintRentalToken(
         landAssetId,
-        dateNow,
+        new anchor.BN(startTime),
         bump,
         Buffer.from(metadataBuffer),
-        landAssetLeafData
+        landAssetLeafData,
+        U64_MAX
       )
       .accountsStrict({
         centralAuthority: centralAuthority,
-        centralizedAccount: centralizedAccount.publicKey,
+        mintingSigner: centralizedAccount.publicKey,
         mint: mintAccount, //alt
         caller: caller.publicKey,
         callerAta: callerAta,
@@ -535,11 +598,19 @@ describe("solana-sky-trade", () => {
         logWrapper: SPL_NOOP_PROGRAM_ID, //alt
         compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID, //alt
         systemProgram: anchor.web3.SystemProgram.programId, //alt
+        eventAuthority,
+        program: program.programId,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID, //alt
         tokenProgram: TOKEN_PROGRAM_ID, //alt
         tokenMetadataProgram: MPL_TOKEN_METADATA_PROGRAM_ID, //alt
         rentEscrow: rent_escrow,
         rentEscrowAta: rent_escrow_Ata,
+        dayBookings,
+        nextDayBookings: null,
+        landPricing,
+        landAvailability,
+        landBookingMode,
+        landStatus,
         landOwner: landOwner,
         landDelegate: landOwner,
       })
@@ -575,11 +646,12 @@ describe("solana-sky-trade", () => {
     await provider.connection
       .sendTransaction(transactionV0)
       .catch((e: SendTransactionError) => {
-        let expectedString = "Error Message: the iso time string is invalid.";
-        //     let actualString=e.logs[(e.logs.length-2-1)] as string
-        //     let ans=actualString.includes(expectedString)
-        //     console.log({ans})
-        //     assert.equal(ans,true);
+        let expectedString =
+          "Error Message: Provided time should be the start of a slot";
+        let actualString = e.logs[e.logs.length - 2 - 1] as string;
+        let ans = actualString.includes(expectedString);
+        console.log({ ans });
+        assert.equal(ans, true);
       });
   });
   it("should fail as time is too far in the future", async () => {
@@ -640,12 +712,15 @@ describe("solana-sky-trade", () => {
 
     umi.use(signerIdentity(callersigner));
 
-    let dateNow = new Date("2025-06-27T19:30:12.738Z").toISOString();
-    console.log({ dateNow });
+    // first 30 minutes slot starting 100 days from now
+    let startTime =
+      Math.ceil((Date.now() / 1000 + 100 * 24 * 60 * 60) / 1800) * 1800;
+    console.log({ startTime });
 
-    let [rent_escrow, bump] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("escrow"), landAssetId.toBytes(), Buffer.from(dateNow)],
-      program.programId
+    let [rent_escrow, bump] = findRentalEscrowPda(
+      program.programId,
+      landAssetId,
+      startTime
     );
 
     const rent_escrow_Ata = associatedAddress({
@@ -653,19 +728,26 @@ describe("solana-sky-trade", () => {
       owner: rent_escrow,
     });
 
+    const dayBookings = findDayBookingsPda(
+      program.programId,
+      landAssetId,
+      startTime
+    );
+
     let leavesDataLength = new anchor.BN(1);
 
     let ix = await program.methods
       .mintRentalToken(
         landAssetId,
-        dateNow,
+        new anchor.BN(startTime),
         bump,
         Buffer.from(metadataBuffer),
-        landAssetLeafData
+        landAssetLeafData,
+        U64_MAX
       )
       .accountsStrict({
         centralAuthority: centralAuthority,
-        centralizedAccount: centralizedAccount.publicKey,
+        mintingSigner: centralizedAccount.publicKey,
         mint: mintAccount, //alt
         caller: caller.publicKey,
         callerAta: callerAta,
@@ -680,11 +762,19 @@ describe("solana-sky-trade", () => {
         logWrapper: SPL_NOOP_PROGRAM_ID, //alt
         compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID, //alt
         systemProgram: anchor.web3.SystemProgram.programId, //alt
+        eventAuthority,
+        program: program.programId,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID, //alt
         tokenProgram: TOKEN_PROGRAM_ID, //alt
         tokenMetadataProgram: MPL_TOKEN_METADATA_PROGRAM_ID, //alt
         rentEscrow: rent_escrow,
         rentEscrowAta: rent_escrow_Ata,
+        dayBookings,
+        nextDayBookings: null,
+        landPricing,
+        landAvailability,
+        landBookingMode,
+        landStatus,
         landOwner: landOwner,
         landDelegate: landOwner,
       })
@@ -722,7 +812,171 @@ describe("solana-sky-trade", () => {
       .catch((e: SendTransactionError) => {
         console.log(e.logs);
         let expectedString =
-          "Error Message: Provided time shouldnt be more than 3 month in future.";
+          "Error Message: Provided time is further in the future than the booking window allows.";
+        let actualString = e.logs[e.logs.length - 2 - 1] as string;
+        let ans = actualString.includes(expectedString);
+        console.log({ ans });
+        assert.equal(ans, true);
+      });
+  });
+
+  it("should fail as the slot is in the past", async () => {
+    // LAND token data
+    const { landAssetLeafData, landAssetProof, landOwner } =
+      await getAssetDataAndProof(landAssetId, umi, provider.connection);
+
+    // RENTAL token data
+    let offChainMetadata = {
+      name: "RENTAL NFT",
+      symbol: "R-NFT",
+      description: "",
+      image: "https://docs.sky.trade/sky-trade-logo.svg",
+      external_url: "https://sky.trade/",
+      metadata: {},
+    };
+
+    let cid = await pinFilesToIPFS(offChainMetadata);
+
+    let metadataBuffer = getMetadataArgsSerializer().serialize({
+      name: "Rental NFT",
+      symbol: "",
+      uri: `ipfs://${cid}/`,
+      creators: [
+        { address: umi.identity.publicKey, verified: true, share: 100 },
+      ],
+      sellerFeeBasisPoints: 0,
+      primarySaleHappened: false,
+      isMutable: false,
+      editionNonce: null,
+      uses: null,
+      collection: {
+        key: publicKey(rentalCollection.publicKey.toString()),
+        verified: true,
+      },
+      tokenProgramVersion: TokenProgramVersion.Original,
+      tokenStandard: TokenStandard.NonFungible,
+    });
+
+    let [collectionMetadata] = findMetadataPda(umi, {
+      mint: publicKey(rentalCollection.publicKey.toString()),
+    });
+
+    let [collectionEdition] = findMasterEditionPda(umi, {
+      mint: publicKey(rentalCollection.publicKey.toString()),
+    });
+
+    const [bubblegumSigner] = PublicKey.findProgramAddressSync(
+      // `collection_cpi` is a custom prefix required by the Bubblegum program
+      [Buffer.from("collection_cpi", "utf8")],
+      new PublicKey(MPL_BUBBLEGUM_PROGRAM_ID)
+    );
+
+    let callersigner = createSignerFromKeypair(umi, {
+      secretKey: caller.secretKey,
+      publicKey: publicKey(caller.publicKey),
+    });
+
+    umi.use(signerIdentity(callersigner));
+
+    // 30 minutes slot that started a day ago
+    let startTime = Math.floor((Date.now() / 1000 - 24 * 60 * 60) / 1800) * 1800;
+    console.log({ startTime });
+
+    let [rent_escrow, bump] = findRentalEscrowPda(
+      program.programId,
+      landAssetId,
+      startTime
+    );
+
+    const rent_escrow_Ata = associatedAddress({
+      mint: mintAccount,
+      owner: rent_escrow,
+    });
+
+    const dayBookings = findDayBookingsPda(
+      program.programId,
+      landAssetId,
+      startTime
+    );
+
+    let leavesDataLength = new anchor.BN(1);
+
+    let ix = await program.methods
+      .mintRentalToken(
+        landAssetId,
+        new anchor.BN(startTime),
+        bump,
+        Buffer.from(metadataBuffer),
+        landAssetLeafData,
+        U64_MAX
+      )
+      .accountsStrict({
+        centralAuthority: centralAuthority,
+        mintingSigner: centralizedAccount.publicKey,
+        mint: mintAccount, //alt
+        caller: caller.publicKey,
+        callerAta: callerAta,
+        rentalMerkleTree: rentalMerkleTree.publicKey,
+        treeConfig: treeConfig,
+        landMerkleTree: landMerkleTree.publicKey,
+        collectionMint: rentalCollection.publicKey.toString(),
+        collectionEdition,
+        collectionMetadata,
+        bubblegumSigner, //alts
+        bubblegumProgram: MPL_BUBBLEGUM_PROGRAM_ID, //alt
+        logWrapper: SPL_NOOP_PROGRAM_ID, //alt
+        compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID, //alt
+        systemProgram: anchor.web3.SystemProgram.programId, //alt
+        eventAuthority,
+        program: program.programId,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID, //alt
+        tokenProgram: TOKEN_PROGRAM_ID, //alt
+        tokenMetadataProgram: MPL_TOKEN_METADATA_PROGRAM_ID, //alt
+        rentEscrow: rent_escrow,
+        rentEscrowAta: rent_escrow_Ata,
+        dayBookings,
+        nextDayBookings: null,
+        landPricing,
+        landAvailability,
+        landBookingMode,
+        landStatus,
+        landOwner: landOwner,
+        landDelegate: landOwner,
+      })
+      .remainingAccounts(landAssetProof)
+      .instruction();
+
+    /*   let [tx, nonceBlock] = await createTxWithNonce(
+      provider.connection,
+      nonceAccount.publicKey,
+      centralizedAccount.publicKey
+    ); */
+    let blockhash = (await provider.connection.getLatestBlockhash()).blockhash;
+
+    let AltAddress = new PublicKey(
+      "62wUn5TNA7UM1MRUNsovi18oR5CvwpPhAysAWu9PzsfR"
+    );
+
+    // get the table from the cluster
+    const lookupTableAccount = (
+      await provider.connection.getAddressLookupTable(AltAddress)
+    ).value;
+    const messageV0 = new TransactionMessage({
+      payerKey: centralizedAccount.publicKey,
+      recentBlockhash: blockhash,
+      instructions: [ix],
+    }).compileToV0Message([lookupTableAccount]);
+
+    const transactionV0 = new VersionedTransaction(messageV0);
+
+    transactionV0.sign([caller, centralizedAccount]);
+    //console.log({txsize:getTxSize(transactionV0, centralizedAccount.publicKey)});
+
+    await provider.connection
+      .sendTransaction(transactionV0)
+      .catch((e: SendTransactionError) => {
+        console.log(e.logs);
+        let expectedString = "Error Message: Rental can't start in the past.";
         let actualString = e.logs[e.logs.length - 2 - 1] as string;
         let ans = actualString.includes(expectedString);
         console.log({ ans });
@@ -730,3 +984,40 @@ describe("solana-sky-trade", () => {
       });
   });
 });
+
+// mirrors `DayBookings::seed_day`
+const findDayBookingsPda = (
+  programId: PublicKey,
+  landAssetId: PublicKey,
+  timestamp: number,
+  dayOffset = 0
+) => {
+  const day = Math.floor(timestamp / 86400);
+
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("bookings"),
+      landAssetId.toBytes(),
+      new anchor.BN(day + dayOffset).toArrayLike(Buffer, "le", 8),
+    ],
+    programId
+  )[0];
+};
+
+// escrows are keyed by their start minute
+const findRentalEscrowPda = (
+  programId: PublicKey,
+  landAssetId: PublicKey,
+  startTime: number
+) => {
+  const startMinute = Math.floor(startTime / 60);
+
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("rental_escrow"),
+      landAssetId.toBytes(),
+      new anchor.BN(startMinute).toArrayLike(Buffer, "le", 8),
+    ],
+    programId
+  );
+};
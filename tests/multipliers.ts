@@ -0,0 +1,75 @@
+import assert from "assert";
+import { RentalEnv, randomSlotStart, rangePrice } from "./utils/rentalEnv";
+
+const HOUR = 60 * 60;
+
+describe("price multipliers", () => {
+  const env = new RentalEnv();
+  let hourMultipliersBps: number[];
+  let weekdayMultipliersBps: number[];
+
+  before(async () => {
+    ({ hourMultipliersBps, weekdayMultipliersBps } = await env.config());
+  });
+
+  after(async () => {
+    await env.updatePricing({ hourMultipliersBps, weekdayMultipliersBps });
+  });
+
+  it("should price each slot with the multipliers of its hour and weekday", async () => {
+    const slotSeconds = await env.slotSeconds();
+    // The second slot starts on the hour
+    const hourStart =
+      Math.ceil(randomSlotStart(slotSeconds, 72 * HOUR, 96 * HOUR) / HOUR) *
+      HOUR;
+    const startTime = hourStart - slotSeconds;
+    const startDate = new Date(hourStart * 1000);
+
+    const hours: number[] = [];
+    for (let hour = 0; hour < 24; hour++) {
+      hours.push(hour == startDate.getUTCHours() ? 20_000 : 10_000);
+    }
+    const weekdays = [15_000, 15_000, 15_000, 15_000, 15_000, 15_000, 15_000];
+    await env.updatePricing({
+      hourMultipliersBps: hours,
+      weekdayMultipliersBps: weekdays,
+    });
+
+    const price = await env.slotBasePrice();
+    const rentEscrow = await env.mintRental(startTime, 2);
+    const { expectedCost } = await env.escrow(rentEscrow);
+
+    const config = await env.config();
+    assert.equal(
+      expectedCost.toString(),
+      rangePrice(config, price, startTime, slotSeconds, 2).toString()
+    );
+    // 1.5x for the first slot, 2x * 1.5x for the second one
+    assert.ok(expectedCost.gt(price.muln(4)));
+    assert.ok(expectedCost.lte(price.muln(9).divn(2)));
+
+    await env.send([await env.cancelRentalIx(rentEscrow)], [env.caller]);
+  });
+
+  it("should price rentals at the base price with neutral multipliers", async () => {
+    const slotSeconds = await env.slotSeconds();
+    const startTime = randomSlotStart(slotSeconds, 72 * HOUR, 96 * HOUR);
+    const hours: number[] = [];
+    for (let hour = 0; hour < 24; hour++) {
+      hours.push(10_000);
+    }
+    const weekdays = [10_000, 10_000, 10_000, 10_000, 10_000, 10_000, 10_000];
+    await env.updatePricing({
+      hourMultipliersBps: hours,
+      weekdayMultipliersBps: weekdays,
+    });
+
+    const price = await env.slotBasePrice();
+    const rentEscrow = await env.mintRental(startTime, 3);
+    const { expectedCost } = await env.escrow(rentEscrow);
+
+    assert.equal(expectedCost.toString(), price.muln(3).toString());
+
+    await env.send([await env.cancelRentalIx(rentEscrow)], [env.caller]);
+  });
+});
@@ -0,0 +1,122 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { SYSTEM_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/native/system";
+import assert from "assert";
+import {
+  RentalEnv,
+  expectError,
+  findLandPda,
+  landAssetId,
+  randomSlotStart,
+} from "./utils/rentalEnv";
+
+const HOUR = 60 * 60;
+
+describe("pause and land suspension", () => {
+  const env = new RentalEnv();
+  let restore: Record<string, unknown>;
+
+  before(async () => {
+    restore = await env.useShortRentals();
+  });
+
+  after(async () => {
+    await setPause(false, false);
+    await setLandStatus(false);
+    await env.updateConfig(restore);
+  });
+
+  const setPause = async (
+    rentalsPaused: boolean,
+    settlementsPaused: boolean,
+    pauseGuardian: Keypair = env.centralizedAccount
+  ) => {
+    const ix = await env.program.methods
+      .setPause(rentalsPaused, settlementsPaused)
+      .accountsStrict({
+        centralAuthority: env.centralAuthority,
+        pauseGuardian: pauseGuardian.publicKey,
+        eventAuthority: env.eventAuthority,
+        program: env.program.programId,
+      })
+      .instruction();
+
+    return env.send([ix], [pauseGuardian]);
+  };
+
+  const setLandStatus = async (
+    suspended: boolean,
+    land: PublicKey = landAssetId
+  ) => {
+    const ix = await env.program.methods
+      .setLandStatus(land, suspended)
+      .accountsStrict({
+        centralAuthority: env.centralAuthority,
+        pauseGuardian: env.centralizedAccount.publicKey,
+        landStatus: findLandPda(env.program.programId, "land_status", land),
+        systemProgram: SYSTEM_PROGRAM_ID,
+        eventAuthority: env.eventAuthority,
+        program: env.program.programId,
+      })
+      .instruction();
+
+    return env.send([ix], []);
+  };
+
+  const futureStart = async () =>
+    randomSlotStart(await env.slotSeconds(), 72 * HOUR, 96 * HOUR);
+
+  it("should only let the pause guardian pause", async () => {
+    await expectError(
+      setPause(true, true, env.caller),
+      "Invalid authority provided!"
+    );
+  });
+
+  it("should refuse new rentals while rentals are paused", async () => {
+    const bookedBefore = await env.mintRental(await futureStart());
+
+    await setPause(true, false);
+    assert.ok((await env.config()).rentalsPaused);
+
+    await expectError(
+      env.mintRental(await futureStart()),
+      "New rentals are paused"
+    );
+    // Existing rentals can still be cancelled
+    await env.send([await env.cancelRentalIx(bookedBefore)], [env.caller]);
+
+    await setPause(false, false);
+    const bookedAfter = await env.mintRental(await futureStart());
+    await env.send([await env.cancelRentalIx(bookedAfter)], [env.caller]);
+  });
+
+  it("should refuse settlements while settlements are paused", async () => {
+    const rentEscrow = await env.mintEndedRental();
+
+    await setPause(false, true);
+    await expectError(
+      env.send([await env.settleBatchIx([rentEscrow])], []),
+      "Settlements are paused"
+    );
+
+    await setPause(false, false);
+    await env.send([await env.settleBatchIx([rentEscrow])], []);
+  });
+
+  it("should refuse rentals and settlements of a suspended land", async () => {
+    const rentEscrow = await env.mintEndedRental();
+
+    await setLandStatus(true);
+    await expectError(
+      env.mintRental(await futureStart()),
+      "Land is suspended"
+    );
+    await expectError(
+      env.send([await env.settleBatchIx([rentEscrow])], []),
+      "Land is suspended"
+    );
+
+    await setLandStatus(false);
+    await env.send([await env.settleBatchIx([rentEscrow])], []);
+  });
+});
@@ -0,0 +1,96 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import assert from "assert";
+import {
+  RentalEnv,
+  expectError,
+  otherLandAssetId,
+  tokenBalance,
+} from "./utils/rentalEnv";
+
+describe("settle_batch", () => {
+  const env = new RentalEnv();
+  let restore: Record<string, unknown>;
+  let escrows: PublicKey[];
+
+  before(async () => {
+    restore = await env.useShortRentals();
+    escrows = [await env.mintEndedRental(), await env.mintEndedRental()];
+  });
+
+  after(async () => {
+    await env.updateConfig(restore);
+  });
+
+  it("should fail when an escrow is passed twice", async () => {
+    await expectError(
+      env.send([await env.settleBatchIx([escrows[0], escrows[0]])], []),
+      "Provided Accounts should be a multiple of 2"
+    );
+  });
+
+  it("should fail when an escrow belongs to another land", async function () {
+    if (!otherLandAssetId) {
+      this.skip();
+    }
+    const otherEscrow = await env.mintEndedRental(env.caller, otherLandAssetId);
+
+    await expectError(
+      env.send([await env.settleBatchIx([escrows[0], otherEscrow])], []),
+      "A require_keys_eq expression was violated"
+    );
+
+    await env.send(
+      [
+        await env.settleBatchIx(
+          [otherEscrow],
+          env.centralizedAccount,
+          null,
+          otherLandAssetId
+        ),
+      ],
+      []
+    );
+  });
+
+  it("should pay the totals of every escrow at once", async () => {
+    const terms = await Promise.all(
+      escrows.map((escrow) => env.escrow(escrow))
+    );
+    const totalCost = terms.reduce(
+      (sum, escrow) => sum.add(escrow.expectedCost),
+      new BN(0)
+    );
+    const totalFee = terms.reduce(
+      (sum, escrow) => sum.add(escrow.feeQuota),
+      new BN(0)
+    );
+
+    const { owner } = await env.leaf(terms[0].landAssetId);
+    const paymentReceiverAta = await env.createAta(
+      await env.paymentReceiver(owner)
+    );
+    const feeAccountAta = await env.createAta(env.feeAccount);
+    const ownerBefore = await tokenBalance(env.connection, paymentReceiverAta);
+    const feeBefore = await tokenBalance(env.connection, feeAccountAta);
+    const { openEscrows } = await env.config();
+
+    await env.send([await env.settleBatchIx(escrows)], []);
+
+    const ownerAfter = await tokenBalance(env.connection, paymentReceiverAta);
+    const feeAfter = await tokenBalance(env.connection, feeAccountAta);
+
+    assert.equal(feeAfter.sub(feeBefore).toString(), totalFee.toString());
+    assert.equal(
+      ownerAfter.sub(ownerBefore).toString(),
+      totalCost.sub(totalFee).toString()
+    );
+    for (const escrow of escrows) {
+      assert.equal(await env.connection.getAccountInfo(escrow), null);
+    }
+    assert.equal(
+      (await env.config()).openEscrows.toString(),
+      openEscrows.subn(escrows.length).toString()
+    );
+  });
+});
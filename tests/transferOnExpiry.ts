@@ -56,6 +56,10 @@ describe("solana-sky-trade", () => {
     [Buffer.from("central_authority")],
     program.programId
   )[0];
+  const eventAuthority = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("__event_authority")],
+    program.programId
+  )[0];
   let mintAccEnv = process.env.MINT_ACCOUNT_ADDRESS;
   const mintAccount = new anchor.web3.PublicKey(mintAccEnv);
 
@@ -180,10 +184,18 @@ console.log({rent_escrow})
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SYSTEM_PROGRAM_ID,
+        eventAuthority,
+        program: program.programId,
         rentEscrow: rent_escrow,
         rentEscrowAta: rent_escrow_Ata,
+        keeper: caller.publicKey,
+        keeperAta: null,
         compressionProgram: new PublicKey(SPL_ACCOUNT_COMPRESSION_PROGRAM_ID),
         merkleTree: assetWithProof.merkleTree,
+        landStatus: anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("land_status"), landAssetId.toBytes()],
+          program.programId
+        )[0],
       })
       .remainingAccounts(proof)
       .instruction();
@@ -0,0 +1,200 @@
+import { BN } from "@coral-xyz/anchor";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { SYSTEM_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/native/system";
+import assert from "assert";
+import { sleep } from "../helper";
+import {
+  BPS_DENOMINATOR,
+  RentalEnv,
+  expectError,
+  randomSlotStart,
+  tokenBalance,
+} from "./utils/rentalEnv";
+
+const HOUR = 60 * 60;
+
+describe("transfer_rental", () => {
+  const env = new RentalEnv();
+  let restore: Record<string, unknown>;
+
+  before(async () => {
+    restore = await env.useShortRentals();
+  });
+
+  after(async () => {
+    await env.updateConfig(restore);
+  });
+
+  const transferRentalIx = async (
+    rentEscrow: PublicKey,
+    price: BN,
+    renter: Keypair,
+    newRenter: Keypair
+  ) => {
+    const { escrow, rental, accounts } = await env.rentalAccounts(rentEscrow);
+    const land = await env.leaf(escrow.landAssetId);
+    const paymentReceiver = await env.paymentReceiver(land.owner);
+
+    return env.program.methods
+      .transferRental(price, land.leafData, rental.leafData, land.proof.length)
+      .accountsStrict({
+        centralAuthority: env.centralAuthority,
+        mint: env.mintAccount,
+        renter: renter.publicKey,
+        renterAta: await env.createAta(renter.publicKey),
+        newRenter: newRenter.publicKey,
+        newRenterAta: await env.createAta(newRenter.publicKey),
+        landOwner: land.owner,
+        landDelegate: land.delegate,
+        paymentReceiver,
+        paymentReceiverAta: await env.createAta(paymentReceiver),
+        rentEscrow,
+        landMerkleTree: env.landMerkleTree.publicKey,
+        mintingSigner: env.centralizedAccount.publicKey,
+        ...accounts,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SYSTEM_PROGRAM_ID,
+        eventAuthority: env.eventAuthority,
+        program: env.program.programId,
+      })
+      .remainingAccounts([...land.proof, ...rental.proof])
+      .instruction();
+  };
+
+  const transferRental = async (
+    rentEscrow: PublicKey,
+    price: BN,
+    renter: Keypair,
+    newRenter: Keypair
+  ) =>
+    env.send(
+      [await transferRentalIx(rentEscrow, price, renter, newRenter)],
+      [renter, newRenter]
+    );
+
+  // Rental leaf once the indexer caught up with its transfer to `owner`
+  const rentalLeafOf = async (assetId: PublicKey, owner: PublicKey) => {
+    for (let i = 0; ; i++) {
+      const leaf = await env.leaf(assetId);
+      if (leaf.owner.equals(owner) || i >= 10) {
+        return leaf;
+      }
+      await sleep(2000);
+    }
+  };
+
+  const futureStart = async () =>
+    randomSlotStart(await env.slotSeconds(), 72 * HOUR, 96 * HOUR);
+
+  it("should only let the renter transfer the rental", async () => {
+    const rentEscrow = await env.mintRental(await futureStart());
+
+    await expectError(
+      transferRental(rentEscrow, new BN(0), Keypair.generate(), env.caller),
+      "A has one constraint was violated"
+    );
+
+    await env.send([await env.cancelRentalIx(rentEscrow)], [env.caller]);
+  });
+
+  it("should fail to transfer a rental that ended", async () => {
+    const rentEscrow = await env.mintEndedRental();
+
+    await expectError(
+      transferRental(rentEscrow, new BN(0), env.caller, Keypair.generate()),
+      "Rental has already ended"
+    );
+
+    await env.send([await env.settleBatchIx([rentEscrow])], []);
+  });
+
+  it("should hand the rental over and pay the seller minus the royalty", async () => {
+    const rentEscrow = await env.mintRental(await futureStart());
+    const newRenter = Keypair.generate();
+
+    // Handed over for free
+    await transferRental(rentEscrow, new BN(0), env.caller, newRenter);
+
+    let escrow = await env.escrow(rentEscrow);
+    assert.ok(escrow.renter.equals(newRenter.publicKey));
+    assert.ok(escrow.renterAta.equals(env.ata(newRenter.publicKey)));
+    let rental = await rentalLeafOf(
+      escrow.rentalAssetId,
+      newRenter.publicKey
+    );
+    assert.ok(rental.owner.equals(newRenter.publicKey));
+    assert.ok(rental.delegate.equals(env.centralizedAccount.publicKey));
+
+    // The former renter can't use it anymore
+    await expectError(
+      env.send([await env.cancelRentalIx(rentEscrow)], [env.caller]),
+      "A has one constraint was violated"
+    );
+
+    // Sold back, the land payment receiver gets the royalty
+    const price = new BN(1_000_000);
+    const { resaleRoyaltyBps } = await env.config();
+    const royalty = price.muln(resaleRoyaltyBps).divn(BPS_DENOMINATOR);
+    const { owner } = await env.leaf(escrow.landAssetId);
+    const paymentReceiverAta = env.ata(await env.paymentReceiver(owner));
+    const sellerAta = env.ata(newRenter.publicKey);
+    const buyerAta = env.ata(env.caller.publicKey);
+    const sellerBefore = await tokenBalance(env.connection, sellerAta);
+    const buyerBefore = await tokenBalance(env.connection, buyerAta);
+    const receiverBefore = await tokenBalance(
+      env.connection,
+      paymentReceiverAta
+    );
+
+    await transferRental(rentEscrow, price, newRenter, env.caller);
+
+    const sellerAfter = await tokenBalance(env.connection, sellerAta);
+    const buyerAfter = await tokenBalance(env.connection, buyerAta);
+    const receiverAfter = await tokenBalance(
+      env.connection,
+      paymentReceiverAta
+    );
+    assert.equal(
+      sellerAfter.sub(sellerBefore).toString(),
+      price.sub(royalty).toString()
+    );
+    assert.equal(buyerBefore.sub(buyerAfter).toString(), price.toString());
+    assert.equal(
+      receiverAfter.sub(receiverBefore).toString(),
+      royalty.toString()
+    );
+
+    escrow = await env.escrow(rentEscrow);
+    assert.ok(escrow.renter.equals(env.caller.publicKey));
+    rental = await rentalLeafOf(escrow.rentalAssetId, env.caller.publicKey);
+    assert.ok(rental.owner.equals(env.caller.publicKey));
+    assert.ok(rental.delegate.equals(env.centralizedAccount.publicKey));
+
+    await env.send([await env.cancelRentalIx(rentEscrow)], [env.caller]);
+  });
+
+  it("should void a resold rental and refund the new renter", async () => {
+    const rentEscrow = await env.mintRental(await futureStart());
+    const newRenter = Keypair.generate();
+    await transferRental(rentEscrow, new BN(0), env.caller, newRenter);
+
+    const { expectedCost, rentalAssetId } = await env.escrow(rentEscrow);
+    await rentalLeafOf(rentalAssetId, newRenter.publicKey);
+    const newRenterAta = env.ata(newRenter.publicKey);
+    const before = await tokenBalance(env.connection, newRenterAta);
+
+    await env.send(
+      [await env.voidRentalIx(rentEscrow, 1, env.centralizedAccount)],
+      []
+    );
+
+    const after = await tokenBalance(env.connection, newRenterAta);
+    assert.equal(after.sub(before).toString(), expectedCost.toString());
+    assert.equal(await env.connection.getAccountInfo(rentEscrow), null);
+  });
+});
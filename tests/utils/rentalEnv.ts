@@ -0,0 +1,899 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import { SolanaSkyTrade } from "../../target/types/solana_sky_trade";
+import { loadKeyPair, pinFilesToIPFS, sleep } from "../../helper";
+import {
+  createSignerFromKeypair,
+  publicKey,
+  signerIdentity,
+} from "@metaplex-foundation/umi";
+import { createUmi } from "@metaplex-foundation/umi-bundle-defaults";
+import {
+  TokenProgramVersion,
+  TokenStandard,
+  findTreeConfigPda,
+  mplBubblegum,
+  MPL_BUBBLEGUM_PROGRAM_ID,
+  getMetadataArgsSerializer,
+  SPL_NOOP_PROGRAM_ID,
+  SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+  getAssetWithProof,
+} from "@metaplex-foundation/mpl-bubblegum";
+import {
+  mplTokenMetadata,
+  findMetadataPda,
+  findMasterEditionPda,
+  MPL_TOKEN_METADATA_PROGRAM_ID,
+} from "@metaplex-foundation/mpl-token-metadata";
+import {
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import {
+  AccountMeta,
+  Keypair,
+  PublicKey,
+  TransactionInstruction,
+  TransactionMessage,
+  VersionedTransaction,
+} from "@solana/web3.js";
+import { ConcurrentMerkleTreeAccount } from "@solana/spl-account-compression";
+import { SYSTEM_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/native/system";
+import { associatedAddress } from "@coral-xyz/anchor/dist/cjs/utils/token";
+import { join } from "path";
+import assert from "assert";
+import "dotenv/config";
+
+export const landAssetId = new PublicKey(
+  "HD6m5GvQRaugE6a4ZAzqL5hB3GqMYLeVvw5CAYktkca4"
+);
+
+// Second land for the tests mixing lands, these are skipped without it
+export const otherLandAssetId = process.env.OTHER_LAND_ASSET_ID
+  ? new PublicKey(process.env.OTHER_LAND_ASSET_ID)
+  : null;
+
+const AltAddress = new PublicKey(
+  "62wUn5TNA7UM1MRUNsovi18oR5CvwpPhAysAWu9PzsfR"
+);
+
+export const BPS_DENOMINATOR = 10_000;
+
+// Largest `u64`, a `max_cost` accepting any price
+export const U64_MAX = new BN("18446744073709551615");
+
+// Raised by the compression program when a leaf doesn't match its owner or delegate
+export const LEAF_MISMATCH = "Concurrent merkle tree error";
+
+export type LeafData = {
+  index: number;
+  nonce: BN;
+  root: number[];
+  hash: number[];
+  creatorHash: number[];
+};
+
+export type Leaf = {
+  leafData: LeafData;
+  proof: AccountMeta[];
+  owner: PublicKey;
+  delegate: PublicKey;
+  merkleTree: PublicKey;
+};
+
+// mirrors `DayBookings::seed_day`
+export const findDayBookingsPda = (
+  programId: PublicKey,
+  landAssetId: PublicKey,
+  timestamp: number,
+  dayOffset = 0
+) => {
+  const day = Math.floor(timestamp / 86400);
+
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("bookings"),
+      landAssetId.toBytes(),
+      new BN(day + dayOffset).toArrayLike(Buffer, "le", 8),
+    ],
+    programId
+  )[0];
+};
+
+// escrows are keyed by their start minute
+export const findRentalEscrowPda = (
+  programId: PublicKey,
+  landAssetId: PublicKey,
+  startTime: number
+) => {
+  const startMinute = Math.floor(startTime / 60);
+
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("rental_escrow"),
+      landAssetId.toBytes(),
+      new BN(startMinute).toArrayLike(Buffer, "le", 8),
+    ],
+    programId
+  );
+};
+
+export const findLandPda = (
+  programId: PublicKey,
+  prefix: string,
+  landAssetId: PublicKey
+) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from(prefix), landAssetId.toBytes()],
+    programId
+  )[0];
+
+/// Waits for `tx` to fail with the custom or anchor error `message`
+export const expectError = async (tx: Promise<unknown>, message: string) => {
+  const logs: string[] = await tx.then(
+    () => assert.fail(`expected the transaction to fail with "${message}"`),
+    (e) => e.logs ?? []
+  );
+
+  assert.ok(
+    logs.some((log) => log.includes(`Error Message: ${message}`)),
+    logs.join("\n")
+  );
+};
+
+/// Start of a random slot between `minSeconds` and `maxSeconds` from now, so reruns on the shared
+/// cluster don't collide with the bookings of previous runs
+export const randomSlotStart = (
+  slotSeconds: number,
+  minSeconds: number,
+  maxSeconds: number
+) => {
+  const now = Math.floor(Date.now() / 1000);
+  const slots = Math.floor((maxSeconds - minSeconds) / slotSeconds);
+  const start =
+    Math.ceil((now + minSeconds) / slotSeconds) * slotSeconds +
+    Math.floor(Math.random() * slots) * slotSeconds;
+
+  return start;
+};
+
+// mirrors `Data::range_price`
+export const rangePrice = (
+  config: {
+    hourMultipliersBps: number[];
+    weekdayMultipliersBps: number[];
+  },
+  price: BN,
+  startTime: number,
+  slotSeconds: number,
+  slotCount: number
+) => {
+  let total = new BN(0);
+  for (let slot = 0; slot < slotCount; slot++) {
+    const slotStart = new Date((startTime + slot * slotSeconds) * 1000);
+    const weekday = (slotStart.getUTCDay() + 6) % 7;
+
+    total = total.add(
+      price
+        .muln(config.hourMultipliersBps[slotStart.getUTCHours()])
+        .divn(BPS_DENOMINATOR)
+        .muln(config.weekdayMultipliersBps[weekday])
+        .divn(BPS_DENOMINATOR)
+    );
+  }
+
+  return total;
+};
+
+export const tokenBalance = async (
+  connection: anchor.web3.Connection,
+  ata: PublicKey
+) => new BN((await connection.getTokenAccountBalance(ata)).value.amount);
+
+/// Shared accounts and helpers of the rental tests, all set up from the `.env` of the devnet
+/// deployment
+export class RentalEnv {
+  provider = anchor.AnchorProvider.env();
+  program: Program<SolanaSkyTrade>;
+  umi = createUmi(this.provider.connection.rpcEndpoint)
+    .use(mplBubblegum())
+    .use(mplTokenMetadata());
+
+  centralizedAccount = loadKeyPair(process.env.CENTRALIZED_ACCOUNT);
+  // caZUFsSZLD8VK8q652FZm3nZWqq4HFncr4pix8sckYb
+  caller = loadKeyPair(join(__dirname, "../../wallets/devnet-keys/caller.json"));
+  mintAccount = new PublicKey(process.env.MINT_ACCOUNT_ADDRESS);
+  feeAccount = new PublicKey(process.env.FEE_ACCOUNT);
+  rentalMerkleTree = loadKeyPair(process.env.RENTAL_MERKLE_TREE);
+  rentalCollection = loadKeyPair(process.env.RENTAL_COLLECTION_MINT);
+  landMerkleTree = loadKeyPair(process.env.LAND_MERKLE_TREE);
+
+  centralAuthority: PublicKey;
+  eventAuthority: PublicKey;
+  treeConfig: PublicKey;
+
+  constructor() {
+    anchor.setProvider(this.provider);
+    this.program = anchor.workspace.SolanaSkyTrade as Program<SolanaSkyTrade>;
+
+    this.centralAuthority = PublicKey.findProgramAddressSync(
+      [Buffer.from("central_authority")],
+      this.program.programId
+    )[0];
+    this.eventAuthority = PublicKey.findProgramAddressSync(
+      [Buffer.from("__event_authority")],
+      this.program.programId
+    )[0];
+    this.treeConfig = new PublicKey(
+      findTreeConfigPda(this.umi, {
+        merkleTree: publicKey(this.rentalMerkleTree.publicKey),
+      })[0]
+    );
+
+    this.umi.use(
+      signerIdentity(
+        createSignerFromKeypair(this.umi, {
+          secretKey: this.centralizedAccount.secretKey,
+          publicKey: publicKey(this.centralizedAccount.publicKey),
+        })
+      )
+    );
+  }
+
+  /// Owner of the land the tests rent, only loaded by the tests of the land owner instructions
+  get landOwner() {
+    return loadKeyPair(process.env.LAND_OWNER);
+  }
+
+  get connection() {
+    return this.provider.connection;
+  }
+
+  ata(owner: PublicKey) {
+    return getAssociatedTokenAddressSync(this.mintAccount, owner, true);
+  }
+
+  async createAta(owner: PublicKey) {
+    return (
+      await getOrCreateAssociatedTokenAccount(
+        this.connection,
+        this.centralizedAccount,
+        this.mintAccount,
+        owner,
+        true
+      )
+    ).address;
+  }
+
+  async config() {
+    return this.program.account.data.fetch(this.centralAuthority);
+  }
+
+  async slotSeconds() {
+    return (await this.config()).slotMinutes * 60;
+  }
+
+  /// Sends `ixs` in a v0 transaction using the lookup table of the deployment
+  async send(
+    ixs: TransactionInstruction[],
+    signers: Keypair[],
+    payer: Keypair = this.centralizedAccount
+  ) {
+    const { blockhash, lastValidBlockHeight } =
+      await this.connection.getLatestBlockhash();
+    const lookupTableAccount = (
+      await this.connection.getAddressLookupTable(AltAddress)
+    ).value;
+
+    const messageV0 = new TransactionMessage({
+      payerKey: payer.publicKey,
+      recentBlockhash: blockhash,
+      instructions: ixs,
+    }).compileToV0Message([lookupTableAccount]);
+
+    const transactionV0 = new VersionedTransaction(messageV0);
+    const uniqueSigners = [payer, ...signers].filter(
+      (signer, i, all) =>
+        all.findIndex((other) => other.publicKey.equals(signer.publicKey)) ===
+        i
+    );
+    transactionV0.sign(uniqueSigners);
+
+    const signature = await this.connection.sendTransaction(transactionV0);
+    const confirmation = await this.connection.confirmTransaction(
+      { signature, blockhash, lastValidBlockHeight },
+      "confirmed"
+    );
+    if (confirmation.value.err) {
+      throw new Error(JSON.stringify(confirmation.value.err));
+    }
+    console.log(`https://explorer.solana.com/tx/${signature}?cluster=devnet`);
+
+    return signature;
+  }
+
+  /// Leaf data and proof of a compressed asset, retried while the indexer catches up
+  async leaf(assetId: PublicKey, retries = 10): Promise<Leaf> {
+    for (let i = 0; ; i++) {
+      try {
+        const assetWithProof = await getAssetWithProof(
+          this.umi,
+          publicKey(assetId)
+        );
+        const merkleTree = new PublicKey(assetWithProof.merkleTree);
+        const canopyDepth = (
+          await ConcurrentMerkleTreeAccount.fromAccountAddress(
+            this.connection,
+            merkleTree
+          )
+        ).getCanopyDepth();
+
+        return {
+          leafData: {
+            index: assetWithProof.index,
+            nonce: new BN(assetWithProof.nonce),
+            root: Array.from(assetWithProof.root),
+            hash: Array.from(assetWithProof.dataHash),
+            creatorHash: Array.from(assetWithProof.creatorHash),
+          },
+          proof: assetWithProof.proof
+            .map((node) => ({
+              pubkey: new PublicKey(node),
+              isSigner: false,
+              isWritable: false,
+            }))
+            .slice(0, assetWithProof.proof.length - canopyDepth),
+          owner: new PublicKey(assetWithProof.leafOwner),
+          delegate: new PublicKey(assetWithProof.leafDelegate),
+          merkleTree,
+        };
+      } catch (e) {
+        if (i >= retries) {
+          throw e;
+        }
+        await sleep(2000);
+      }
+    }
+  }
+
+  /// Wallet paid for the land, the auction creator while the land is listed
+  async paymentReceiver(landOwner: PublicKey) {
+    const leafOwnerData = await this.connection.getAccountInfo(landOwner);
+
+    if (leafOwnerData.owner.equals(SYSTEM_PROGRAM_ID)) {
+      return landOwner;
+    }
+    if (leafOwnerData.owner.toString() == process.env.AH_PROGRAM_ADDRESS) {
+      let dataBeforeSeller = 8 + 10 + 32 + 32 + 8 + 8;
+
+      return new PublicKey(
+        leafOwnerData.data.slice(dataBeforeSeller, dataBeforeSeller + 32)
+      );
+    }
+    throw new Error("Invalid leaf owner");
+  }
+
+  async rentalMetadata() {
+    let offChainMetadata = {
+      name: "RENTAL NFT",
+      symbol: "R-NFT",
+      description: "",
+      image: "https://docs.sky.trade/sky-trade-logo.svg",
+      external_url: "https://sky.trade/",
+      metadata: {},
+    };
+
+    let cid = await pinFilesToIPFS(offChainMetadata);
+
+    return Buffer.from(
+      getMetadataArgsSerializer().serialize({
+        name: "Rental NFT",
+        symbol: "",
+        uri: `ipfs://${cid}/`,
+        creators: [
+          { address: this.umi.identity.publicKey, verified: true, share: 100 },
+        ],
+        sellerFeeBasisPoints: 0,
+        primarySaleHappened: false,
+        isMutable: false,
+        editionNonce: null,
+        uses: null,
+        collection: {
+          key: publicKey(this.rentalCollection.publicKey.toString()),
+          verified: true,
+        },
+        tokenProgramVersion: TokenProgramVersion.Original,
+        tokenStandard: TokenStandard.NonFungible,
+      })
+    );
+  }
+
+  /// Accounts shared by the instructions minting a rental cNFT
+  collectionAccounts() {
+    const mint = publicKey(this.rentalCollection.publicKey.toString());
+
+    return {
+      collectionMint: this.rentalCollection.publicKey,
+      collectionMetadata: new PublicKey(findMetadataPda(this.umi, { mint })[0]),
+      collectionEdition: new PublicKey(
+        findMasterEditionPda(this.umi, { mint })[0]
+      ),
+      bubblegumSigner: PublicKey.findProgramAddressSync(
+        // `collection_cpi` is a custom prefix required by the Bubblegum program
+        [Buffer.from("collection_cpi", "utf8")],
+        new PublicKey(MPL_BUBBLEGUM_PROGRAM_ID)
+      )[0],
+      bubblegumProgram: MPL_BUBBLEGUM_PROGRAM_ID,
+      logWrapper: SPL_NOOP_PROGRAM_ID,
+      compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+      tokenMetadataProgram: MPL_TOKEN_METADATA_PROGRAM_ID,
+    };
+  }
+
+  /// `DayBookings` of a booking, the next day only when it continues past midnight
+  dayBookings(landAssetId: PublicKey, startTime: number, endTime: number) {
+    const crossesMidnight =
+      Math.floor((endTime - 1) / 86400) > Math.floor(startTime / 86400);
+
+    return {
+      dayBookings: findDayBookingsPda(
+        this.program.programId,
+        landAssetId,
+        startTime
+      ),
+      nextDayBookings: crossesMidnight
+        ? findDayBookingsPda(this.program.programId, landAssetId, startTime, 1)
+        : null,
+    };
+  }
+
+  /// Instruction booking `slotCount` slots of the land from `startTime` for `renter`, `overrides`
+  /// replaces some of its accounts and `maxCost` caps the price
+  async mintRentalIx(
+    startTime: number,
+    slotCount = 1,
+    renter: Keypair = this.caller,
+    land: PublicKey = landAssetId,
+    overrides: Record<string, PublicKey | null> = {},
+    maxCost: BN = U64_MAX
+  ) {
+    const { leafData, proof, owner, delegate } = await this.leaf(land);
+    const slotSeconds = await this.slotSeconds();
+    const [rentEscrow, bump] = findRentalEscrowPda(
+      this.program.programId,
+      land,
+      startTime
+    );
+
+    return this.program.methods
+      .mintRentalRange(
+        land,
+        new BN(startTime),
+        slotCount,
+        bump,
+        await this.rentalMetadata(),
+        leafData,
+        maxCost
+      )
+      .accountsStrict({
+        centralAuthority: this.centralAuthority,
+        mint: this.mintAccount,
+        mintingSigner: this.centralizedAccount.publicKey,
+        caller: renter.publicKey,
+        callerAta: this.ata(renter.publicKey),
+        rentEscrow,
+        rentEscrowAta: associatedAddress({
+          mint: this.mintAccount,
+          owner: rentEscrow,
+        }),
+        ...this.dayBookings(land, startTime, startTime + slotCount * slotSeconds),
+        landPricing: findLandPda(this.program.programId, "land_pricing", land),
+        landAvailability: findLandPda(
+          this.program.programId,
+          "land_availability",
+          land
+        ),
+        landBookingMode: findLandPda(
+          this.program.programId,
+          "land_booking_mode",
+          land
+        ),
+        landStatus: findLandPda(this.program.programId, "land_status", land),
+        rentalMerkleTree: this.rentalMerkleTree.publicKey,
+        treeConfig: this.treeConfig,
+        landMerkleTree: this.landMerkleTree.publicKey,
+        ...this.collectionAccounts(),
+        landOwner: owner,
+        landDelegate: delegate,
+        systemProgram: SYSTEM_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        eventAuthority: this.eventAuthority,
+        program: this.program.programId,
+        ...overrides,
+      })
+      .remainingAccounts(proof)
+      .instruction();
+  }
+
+  /// Books `slotCount` slots of the land from `startTime` and returns the escrow
+  async mintRental(
+    startTime: number,
+    slotCount = 1,
+    renter: Keypair = this.caller,
+    land: PublicKey = landAssetId
+  ) {
+    await this.send(
+      [await this.mintRentalIx(startTime, slotCount, renter, land)],
+      [renter]
+    );
+
+    return findRentalEscrowPda(this.program.programId, land, startTime)[0];
+  }
+
+  async escrow(rentEscrow: PublicKey) {
+    return this.program.account.rentalEscrow.fetch(rentEscrow);
+  }
+
+  /// Sets the opening hours and blackouts of the land as `landOwner`
+  async setLandAvailability(
+    weeklyHours: number[],
+    blackouts: { startTime: BN; endTime: BN }[],
+    landOwner: Keypair = this.landOwner,
+    land: PublicKey = landAssetId
+  ) {
+    const { leaf, accounts } = await this.landSettingAccounts(landOwner, land);
+    const ix = await this.program.methods
+      .setLandAvailability(land, weeklyHours, blackouts, leaf.leafData)
+      .accountsStrict({
+        ...accounts,
+        landAvailability: findLandPda(
+          this.program.programId,
+          "land_availability",
+          land
+        ),
+      })
+      .remainingAccounts(leaf.proof)
+      .instruction();
+
+    return this.send([ix], [landOwner]);
+  }
+
+  /// Applies `changes` with `update_config`, every other setting unchanged
+  async updateConfig(changes: Record<string, unknown>) {
+    const config = await this.config();
+    const ix = await this.program.methods
+      .updateConfig({
+        auctionHouseAddress: null,
+        disputeWindowSeconds: null,
+        arbiter: null,
+        authorityTimelockSeconds: null,
+        mintAddress: null,
+        baseCost: null,
+        royaltiesReceiver: null,
+        mintCreator: null,
+        verificationCreator: null,
+        pricingAdmin: null,
+        feeAdmin: null,
+        mintingSigner: null,
+        pauseGuardian: null,
+        slotMinutes: null,
+        maxAdvanceSeconds: null,
+        minLeadSeconds: null,
+        runningSlotBookable: null,
+        ...changes,
+      })
+      .accountsStrict({
+        centralAuthority: this.centralAuthority,
+        centralizedAccount: this.centralizedAccount.publicKey,
+        systemProgram: SYSTEM_PROGRAM_ID,
+        mintAccount: config.mintAddress,
+        eventAuthority: this.eventAuthority,
+        program: this.program.programId,
+      })
+      .instruction();
+
+    return this.send([ix], []);
+  }
+
+  /// Applies `changes` with `update_pricing`, every other price unchanged
+  async updatePricing(changes: Record<string, unknown>) {
+    const ix = await this.program.methods
+      .updatePricing({
+        baseCost: null,
+        hourMultipliersBps: null,
+        weekdayMultipliersBps: null,
+        cancellationTiers: null,
+        ...changes,
+      })
+      .accountsStrict({
+        centralAuthority: this.centralAuthority,
+        pricingAdmin: this.centralizedAccount.publicKey,
+        eventAuthority: this.eventAuthority,
+        program: this.program.programId,
+      })
+      .instruction();
+
+    return this.send([ix], []);
+  }
+
+  /// Applies `changes` with `update_fees`, every other fee setting unchanged
+  async updateFees(changes: Record<string, unknown>) {
+    const ix = await this.program.methods
+      .updateFees({
+        adminQuotaBps: null,
+        feeAccount: null,
+        keeperRewardBps: null,
+        rentToKeeper: null,
+        resaleRoyaltyBps: null,
+        ...changes,
+      })
+      .accountsStrict({
+        centralAuthority: this.centralAuthority,
+        feeAdmin: this.centralizedAccount.publicKey,
+        eventAuthority: this.eventAuthority,
+        program: this.program.programId,
+      })
+      .instruction();
+
+    return this.send([ix], []);
+  }
+
+  /// Price of a slot of the land before multipliers, mirrors `LandPricing::price_for`
+  async slotBasePrice(land: PublicKey = landAssetId) {
+    const config = await this.config();
+    const landPricing = await this.program.account.landPricing.fetchNullable(
+      findLandPda(this.program.programId, "land_pricing", land)
+    );
+    const { owner } = await this.leaf(land);
+
+    return landPricing && landPricing.owner.equals(owner)
+      ? landPricing.price
+      : config.baseCost;
+  }
+
+  /// Books the slot in progress, which the config has to allow
+  async mintRunningRental(renter: Keypair = this.caller) {
+    const config = await this.config();
+    if (!config.runningSlotBookable || !config.minLeadSeconds.isZero()) {
+      await this.updateConfig({
+        runningSlotBookable: true,
+        minLeadSeconds: new BN(0),
+      });
+    }
+
+    const slotSeconds = config.slotMinutes * 60;
+    const startTime =
+      Math.floor(Date.now() / 1000 / slotSeconds) * slotSeconds;
+
+    return { rentEscrow: await this.mintRental(startTime, 1, renter), startTime };
+  }
+
+  /// Leaf of the land and the accounts shared by the instructions `landOwner` signs to configure it
+  async landSettingAccounts(
+    landOwner: Keypair = this.landOwner,
+    land: PublicKey = landAssetId
+  ) {
+    const leaf = await this.leaf(land);
+
+    return {
+      leaf,
+      accounts: {
+        centralAuthority: this.centralAuthority,
+        landOwner: landOwner.publicKey,
+        landDelegate: leaf.delegate,
+        landMerkleTree: this.landMerkleTree.publicKey,
+        compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+        systemProgram: SYSTEM_PROGRAM_ID,
+        eventAuthority: this.eventAuthority,
+        program: this.program.programId,
+      },
+    };
+  }
+
+  /// Sets the slot price of the land as `landOwner`
+  async setLandPricing(
+    price: BN,
+    landOwner: Keypair = this.landOwner,
+    land: PublicKey = landAssetId
+  ) {
+    const { leaf, accounts } = await this.landSettingAccounts(landOwner, land);
+    const ix = await this.program.methods
+      .setLandPricing(land, price, leaf.leafData)
+      .accountsStrict({
+        ...accounts,
+        landPricing: findLandPda(this.program.programId, "land_pricing", land),
+      })
+      .remainingAccounts(leaf.proof)
+      .instruction();
+
+    return this.send([ix], [landOwner]);
+  }
+
+  /// Switches to one minute slots bookable while running and no dispute window, so rentals end
+  /// within a test. Returns the settings to restore with `updateConfig`.
+  async useShortRentals() {
+    const config = await this.config();
+    await this.updateConfig({
+      slotMinutes: 1,
+      runningSlotBookable: true,
+      minLeadSeconds: new BN(0),
+      disputeWindowSeconds: new BN(0),
+    });
+
+    return {
+      slotMinutes: config.slotMinutes,
+      runningSlotBookable: config.runningSlotBookable,
+      minLeadSeconds: config.minLeadSeconds,
+      disputeWindowSeconds: config.disputeWindowSeconds,
+    };
+  }
+
+  /// Books the slot in progress and waits for it to end
+  async mintEndedRental(
+    renter: Keypair = this.caller,
+    land: PublicKey = landAssetId
+  ) {
+    const slotSeconds = await this.slotSeconds();
+    const startTime =
+      Math.floor(Date.now() / 1000 / slotSeconds) * slotSeconds;
+    const rentEscrow = await this.mintRental(startTime, 1, renter, land);
+
+    // Leaves some room for the cluster clock to catch up
+    await sleep((startTime + slotSeconds + 5) * 1000 - Date.now());
+
+    return rentEscrow;
+  }
+
+  /// Settles the ended `escrows` of a land, rewarding `keeper` when its ATA is passed
+  async settleBatchIx(
+    escrows: PublicKey[],
+    keeper: Keypair = this.centralizedAccount,
+    keeperAta: PublicKey | null = null,
+    land: PublicKey = landAssetId
+  ) {
+    const { leafData, proof, owner, delegate } = await this.leaf(land);
+    const paymentReceiver = await this.paymentReceiver(owner);
+
+    return this.program.methods
+      .settleBatch(leafData, proof.length)
+      .accountsStrict({
+        mint: this.mintAccount,
+        centralAuthority: this.centralAuthority,
+        feeAccount: this.feeAccount,
+        feeAccountAta: await this.createAta(this.feeAccount),
+        landOwner: owner,
+        landDelegate: delegate,
+        paymentReceiver,
+        paymentReceiverAta: await this.createAta(paymentReceiver),
+        keeper: keeper.publicKey,
+        keeperAta,
+        merkleTree: this.landMerkleTree.publicKey,
+        landStatus: findLandPda(this.program.programId, "land_status", land),
+        compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SYSTEM_PROGRAM_ID,
+        eventAuthority: this.eventAuthority,
+        program: this.program.programId,
+      })
+      .remainingAccounts([
+        ...proof,
+        ...escrows
+          .map((escrow) => [
+            { pubkey: escrow, isSigner: false, isWritable: true },
+            {
+              pubkey: associatedAddress({
+                mint: this.mintAccount,
+                owner: escrow,
+              }),
+              isSigner: false,
+              isWritable: true,
+            },
+          ])
+          .reduce((all, pair) => all.concat(pair), []),
+      ])
+      .instruction();
+  }
+
+  /// Accounts to burn, transfer or delegate the rental cNFT of an escrow
+  async rentalAccounts(rentEscrow: PublicKey) {
+    const escrow = await this.escrow(rentEscrow);
+    const rental = await this.leaf(escrow.rentalAssetId);
+
+    return {
+      escrow,
+      rental,
+      accounts: {
+        rentalDelegate: rental.delegate,
+        rentalMerkleTree: escrow.rentalMerkleTree,
+        treeConfig: this.treeConfig,
+        bubblegumProgram: MPL_BUBBLEGUM_PROGRAM_ID,
+        logWrapper: SPL_NOOP_PROGRAM_ID,
+        compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+      },
+    };
+  }
+
+  /// Cancels a rental of `renter` ahead of its start
+  async cancelRentalIx(rentEscrow: PublicKey, renter: Keypair = this.caller) {
+    const { escrow, rental, accounts } = await this.rentalAccounts(rentEscrow);
+    const land = await this.leaf(escrow.landAssetId);
+    const paymentReceiver = await this.paymentReceiver(land.owner);
+
+    return this.program.methods
+      .cancelRental(land.leafData, rental.leafData, land.proof.length)
+      .accountsStrict({
+        centralAuthority: this.centralAuthority,
+        mint: this.mintAccount,
+        renter: renter.publicKey,
+        renterAta: this.ata(renter.publicKey),
+        feeAccount: this.feeAccount,
+        feeAccountAta: await this.createAta(this.feeAccount),
+        landOwner: land.owner,
+        landDelegate: land.delegate,
+        paymentReceiver,
+        paymentReceiverAta: await this.createAta(paymentReceiver),
+        rentEscrow,
+        rentEscrowAta: associatedAddress({
+          mint: this.mintAccount,
+          owner: rentEscrow,
+        }),
+        ...this.dayBookings(
+          escrow.landAssetId,
+          escrow.startTime.toNumber(),
+          escrow.endTime.toNumber()
+        ),
+        landMerkleTree: this.landMerkleTree.publicKey,
+        ...accounts,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SYSTEM_PROGRAM_ID,
+        eventAuthority: this.eventAuthority,
+        program: this.program.programId,
+      })
+      .remainingAccounts([...land.proof, ...rental.proof])
+      .instruction();
+  }
+
+  /// Voids a rental as the pause guardian, `overrides` replaces some of its accounts
+  async voidRentalIx(
+    rentEscrow: PublicKey,
+    reason = 0,
+    pauseGuardian: Keypair = this.centralizedAccount,
+    overrides: Record<string, PublicKey | null> = {}
+  ) {
+    const { escrow, rental, accounts } = await this.rentalAccounts(rentEscrow);
+
+    return this.program.methods
+      .voidRental(rental.leafData, reason)
+      .accountsStrict({
+        centralAuthority: this.centralAuthority,
+        mint: this.mintAccount,
+        pauseGuardian: pauseGuardian.publicKey,
+        renter: escrow.renter,
+        renterAta: escrow.renterAta,
+        feeAccount: this.feeAccount,
+        rentEscrow,
+        rentEscrowAta: associatedAddress({
+          mint: this.mintAccount,
+          owner: rentEscrow,
+        }),
+        ...this.dayBookings(
+          escrow.landAssetId,
+          escrow.startTime.toNumber(),
+          escrow.endTime.toNumber()
+        ),
+        ...accounts,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SYSTEM_PROGRAM_ID,
+        eventAuthority: this.eventAuthority,
+        program: this.program.programId,
+        ...overrides,
+      })
+      .remainingAccounts(rental.proof)
+      .instruction();
+  }
+}
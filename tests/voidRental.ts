@@ -0,0 +1,105 @@
+import { PublicKey } from "@solana/web3.js";
+import assert from "assert";
+import {
+  RentalEnv,
+  expectError,
+  randomSlotStart,
+  tokenBalance,
+} from "./utils/rentalEnv";
+
+const HOUR = 60 * 60;
+
+describe("void_rental", () => {
+  const env = new RentalEnv();
+  let restore: Record<string, unknown>;
+
+  before(async () => {
+    restore = await env.useShortRentals();
+  });
+
+  after(async () => {
+    await env.updateConfig(restore);
+  });
+
+  const futureStart = async () =>
+    randomSlotStart(await env.slotSeconds(), 72 * HOUR, 96 * HOUR);
+
+  // Voids the rental and checks the renter got its whole payment back
+  const voidAndCheckRefund = async (
+    rentEscrow: PublicKey,
+    overrides: Record<string, PublicKey | null> = {}
+  ) => {
+    const { expectedCost, renterAta } = await env.escrow(rentEscrow);
+    const renterBefore = await tokenBalance(env.connection, renterAta);
+
+    await env.send(
+      [
+        await env.voidRentalIx(
+          rentEscrow,
+          1,
+          env.centralizedAccount,
+          overrides
+        ),
+      ],
+      []
+    );
+
+    const renterAfter = await tokenBalance(env.connection, renterAta);
+    assert.equal(
+      renterAfter.sub(renterBefore).toString(),
+      expectedCost.toString()
+    );
+    assert.equal(await env.connection.getAccountInfo(rentEscrow), null);
+  };
+
+  it("should only let the pause guardian void a rental", async () => {
+    const rentEscrow = await env.mintRental(await futureStart());
+
+    await expectError(
+      env.send(
+        [await env.voidRentalIx(rentEscrow, 1, env.caller)],
+        [env.caller]
+      ),
+      "Invalid authority provided!"
+    );
+
+    await env.send([await env.cancelRentalIx(rentEscrow)], [env.caller]);
+  });
+
+  it("should refund an upcoming rental in full and free its slots", async () => {
+    const startTime = await futureStart();
+    const rentEscrow = await env.mintRental(startTime, 2);
+
+    await expectError(
+      env.send(
+        [
+          await env.voidRentalIx(rentEscrow, 1, env.centralizedAccount, {
+            dayBookings: null,
+          }),
+        ],
+        []
+      ),
+      "Day bookings account is required while the rental has not ended"
+    );
+
+    await voidAndCheckRefund(rentEscrow);
+
+    const rebooked = await env.mintRental(startTime, 2);
+    await env.send([await env.cancelRentalIx(rebooked)], [env.caller]);
+  });
+
+  it("should refund a running rental in full", async () => {
+    const { rentEscrow } = await env.mintRunningRental();
+
+    await voidAndCheckRefund(rentEscrow);
+  });
+
+  it("should void an ended rental without its day bookings", async () => {
+    const rentEscrow = await env.mintEndedRental();
+
+    await voidAndCheckRefund(rentEscrow, {
+      dayBookings: null,
+      nextDayBookings: null,
+    });
+  });
+});